@@ -40,7 +40,7 @@ dx clean
 dx bundle --platform android --release
 
 # into the release folder
-cd target/dx/stay-ahead/release/android/app/
+cd target/dx/stay_ahead/release/android/app/
 
 # do clean and replace the icons, then build
 ./gradlew clean
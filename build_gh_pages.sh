@@ -12,7 +12,7 @@ mv Dioxus.toml.bak Dioxus.toml
 
 git checkout gh-pages
 rm -rf assets index.html wasm
-cp -r target/dx/stay-ahead/release/web/public/* .
+cp -r target/dx/stay_ahead/release/web/public/* .
 
 # Add Simple Analytics
 file="index.html"
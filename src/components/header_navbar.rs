@@ -12,11 +12,9 @@ pub fn HeaderNavbar() -> Element {
     let current_route = use_route::<Route>();
 
     let no_save_app_state = use_context::<NoSaveAppState>();
-    let mut sync_msg = no_save_app_state.sync_msg;
-    let mut sync_mode = no_save_app_state.sync_mode;
-    let mut fire_push_after_deletion = no_save_app_state.fire_push_after_deletion;
-
-    let app_state = use_context::<AppState>();
+    let sync_mode = no_save_app_state.sync_mode;
+    let sync_conflicts = no_save_app_state.sync_conflicts;
+    let sync_outbox = use_context::<AppState>().sync_outbox;
 
     let current_route = use_route::<Route>();
 
@@ -27,33 +25,6 @@ pub fn HeaderNavbar() -> Element {
         "bg-white border border-gray-300 rounded-md shadow-sm text-blue-900 hover:text-blue-900";
     let inactive_class_str = "opacity-50 hover:opacity-100 hover:text-blue-900";
 
-    use_effect(move || {
-        if fire_push_after_deletion() {
-            spawn({
-                sync_mode.set(SyncMode::Pushing);
-                async move {
-                    let mut app_state = use_context::<AppState>();
-                    match app_state.push().await {
-                        Ok(_) => {
-                            sync_msg.set(
-                                "✅ Automatic push was successful after deleting a task!"
-                                    .to_string(),
-                            );
-                            sync_mode.set(SyncMode::InSync);
-                        }
-                        Err(e) => {
-                            sync_msg.set(format!(
-                                "⚠️ Automatic push failed after deleting a task: {}",
-                                e
-                            ));
-                            sync_mode.set(SyncMode::NotSynced);
-                        }
-                    }
-                }
-            });
-        }
-    });
-
     rsx! {
         div {
             class: "min-h-screen relative",
@@ -175,6 +146,7 @@ pub fn HeaderNavbar() -> Element {
                                 src: ICON_SETTING,
                                 class: {
                                     match sync_mode() {
+                                        _ if !sync_conflicts().is_empty() => format!("{} bg-red-400 rounded-full", icon_css),
                                         SyncMode::NotSynced => format!("{} opacity-20 rounded-full", icon_css),
                                         SyncMode::Pushing => format!("{} bg-orange-200 rounded-full animate-spin", icon_css),
                                         SyncMode::Pulling => format!("{} bg-orange-200 rounded-full animate-spin", icon_css),
@@ -184,6 +156,13 @@ pub fn HeaderNavbar() -> Element {
                                 },
                                 alt: "Sync"
                             },
+                            if !sync_outbox().is_empty() {
+                                span {
+                                    class: "text-xs font-semibold text-white bg-amber-500 rounded-full px-1.5",
+                                    title: "Changes waiting to be pushed",
+                                    "{sync_outbox().len()}"
+                                }
+                            }
                             span {
                                 class: "hidden sm:inline",
                                 " Sync"
@@ -1,11 +1,23 @@
 mod header_navbar;
 mod page_about;
+mod page_activity_calendar;
+mod page_action_history;
 mod page_action_log;
+mod page_backup_restore;
+mod page_gist_history;
+mod page_recovery;
 mod page_setting;
 mod page_task_create;
+mod page_task_edit;
 mod page_task_list;
 mod page_task_visual;
+mod setting_folder;
+mod setting_gist;
+mod setting_pairing;
+mod setting_s3;
+mod setting_webdav;
 mod css_preset;
 
 mod routes;
+pub use page_recovery::Recovery;
 pub use routes::Route;
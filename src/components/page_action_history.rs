@@ -0,0 +1,211 @@
+use crate::states::generate_date_range;
+use crate::Route;
+use crate::{AppState, NoSaveAppState, SyncCoordinator, SyncMode};
+use chrono::{Datelike, Local, NaiveDate};
+use dioxus::prelude::*;
+use super::css_preset::*;
+
+#[component]
+pub fn ActionHistory(id: i64) -> Element {
+    let no_save_app_state = use_context::<NoSaveAppState>();
+    let sync_mode = no_save_app_state.sync_mode;
+    let sync_coordinator = use_context::<SyncCoordinator>();
+
+    let mut app_state = use_context::<AppState>();
+    let navigator = use_navigator();
+
+    let mut pending_removal = use_signal::<Option<i64>>(|| None);
+    let mut edit_return_msg = use_signal(|| "".to_string());
+
+    let task = (app_state.tasks)().and_then(|tasks| tasks.get(&id).cloned());
+
+    if task.is_none() {
+        return rsx! {
+            div {
+                class: CSS_CONTENT_CARD,
+                p { "Task not found." }
+            }
+        };
+    }
+
+    let task = task.unwrap();
+    let count_accum = task.count_accum();
+
+    let mut logs = task.logs.clone();
+    logs.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+    let enable_edit = sync_mode() != SyncMode::Pushing && sync_mode() != SyncMode::Pulling;
+
+    // Entries can only be moved to an effective day of the task up to today, like when logging
+    let last_date = task.end.min(Local::now().date_naive());
+    let valid_dates = generate_date_range(task.start, last_date, task.effective_dow.clone());
+
+    rsx! {
+        div {
+            class: CSS_CONTENT_CARD,
+
+            h2 {
+                class: "text-xl font-bold text-center",
+                { format!("🕘 History of \"{}\"", task.label()) }
+            }
+
+            p {
+                class: "text-center text-gray-600",
+                "Done so far: {count_accum:.1} {task.unit}"
+            }
+
+            if logs.is_empty() {
+                p {
+                    class: "text-center text-gray-500",
+                    "Nothing has been logged for this task yet."
+                }
+            } else {
+                div {
+                    class: "flex justify-center",
+                    button {
+                        disabled: "{!enable_edit}",
+                        class: format_args!(
+                            "font-semibold py-2 px-4 rounded transition-colors duration-300 {}",
+                            if enable_edit {
+                                "bg-amber-600 hover:bg-amber-700 text-white cursor-pointer"
+                            } else {
+                                "bg-gray-300 text-gray-500 cursor-not-allowed"
+                            }
+                        ),
+                        onclick: move |_| {
+                            let mut tasks_write = app_state.tasks.write();
+                            if let Some(tasks_mut) = tasks_write.as_mut() {
+                                if let Some(task) = tasks_mut.get_mut(&id) {
+                                    if let Some(entry) = task.undo_last_log() {
+                                        edit_return_msg.set(format!(
+                                            "↩️ Undid the submission of {} on {}",
+                                            entry.amount,
+                                            entry.timestamp.format("%Y-%m-%d"),
+                                        ));
+                                        sync_coordinator.request_push("Edited the history");
+                                    }
+                                }
+                            }
+                        },
+                        "↩️ Undo Last Submission"
+                    }
+                }
+
+                if !edit_return_msg().is_empty() {
+                    p {
+                        class: "text-center text-sm text-gray-700",
+                        "{edit_return_msg}"
+                    }
+                }
+
+                div {
+                    class: "grid grid-cols-1 gap-2",
+                    {
+                        logs.into_iter().map(|entry| {
+                            let log_id = entry.id;
+                            let old_time = entry.timestamp.time();
+                            let valid_dates = valid_dates.clone();
+
+                            rsx! {
+                                div {
+                                    key: "{log_id}",
+                                    class: "grid grid-cols-3 gap-2 items-center p-2 border border-gray-200 rounded-lg",
+
+                                    input {
+                                        r#type: "date",
+                                        disabled: !enable_edit,
+                                        class: "border border-gray-300 rounded-md shadow-sm p-2",
+                                        min: "{task.start}",
+                                        max: "{last_date}",
+                                        value: "{entry.timestamp.format(\"%Y-%m-%d\")}",
+                                        onchange: move |e| {
+                                            if let Ok(date) = NaiveDate::parse_from_str(&e.value(), "%Y-%m-%d") {
+                                                if !valid_dates.contains(&date) {
+                                                    edit_return_msg.set(format!("⚠️ {} [{}] is not an effective day of this task up to today", date, date.weekday()));
+                                                    return;
+                                                }
+
+                                                edit_return_msg.set(String::new());
+                                                let mut tasks_write = app_state.tasks.write();
+                                                if let Some(tasks_mut) = tasks_write.as_mut() {
+                                                    if let Some(task) = tasks_mut.get_mut(&id) {
+                                                        if let Some(entry) = task.logs.iter_mut().find(|entry| entry.id == log_id) {
+                                                            entry.timestamp = date.and_time(old_time);
+                                                            sync_coordinator.request_push("Edited the history");
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+
+                                    input {
+                                        r#type: "number",
+                                        min: "0",
+                                        step: "any",
+                                        disabled: !enable_edit,
+                                        class: "border border-gray-300 rounded-md shadow-sm p-2",
+                                        value: "{entry.amount}",
+                                        onchange: move |e| {
+                                            if let Ok(amount) = e.value().parse::<f32>() {
+                                                if amount > 0.0_f32 {
+                                                    let mut tasks_write = app_state.tasks.write();
+                                                    if let Some(tasks_mut) = tasks_write.as_mut() {
+                                                        if let Some(task) = tasks_mut.get_mut(&id) {
+                                                            if let Some(entry) = task.logs.iter_mut().find(|entry| entry.id == log_id) {
+                                                                entry.amount = amount;
+                                                                sync_coordinator.request_push("Edited the history");
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+
+                                    button {
+                                        disabled: !enable_edit,
+                                        class: "bg-red-100 hover:bg-red-400 text-white font-medium py-2 px-4 rounded-lg transition-all cursor-pointer",
+                                        onclick: move |_| {
+                                            if pending_removal() != Some(log_id) {
+                                                pending_removal.set(Some(log_id));
+                                            } else {
+                                                let mut tasks_write = app_state.tasks.write();
+                                                if let Some(tasks_mut) = tasks_write.as_mut() {
+                                                    if let Some(task) = tasks_mut.get_mut(&id) {
+                                                        task.remove_log(log_id);
+                                                        sync_coordinator.request_push("Edited the history");
+                                                    }
+                                                }
+                                                pending_removal.set(None);
+                                            }
+                                        },
+                                        if pending_removal() == Some(log_id) {"Confirm Removing"} else {"🗑 Remove"}
+                                    }
+
+                                    if let Some(note) = &entry.note {
+                                        p {
+                                            class: "col-span-3 text-sm text-gray-600",
+                                            "📝 {note}"
+                                        }
+                                    }
+                                }
+                            }
+                        })
+                    }
+                }
+            }
+
+            div {
+                class: "flex justify-center",
+                button {
+                    class: "bg-gray-100 text-gray-800 font-medium py-2 px-4 rounded-lg transition-all hover:ring hover:ring-gray-300 hover:ring-offset-2 cursor-pointer",
+                    onclick: move |_| {
+                        navigator.push(Route::Director { pagename: format!("TaskVisual/{}", id) });
+                    },
+                    "⬅️ Back to Task",
+                }
+            }
+        }
+    }
+}
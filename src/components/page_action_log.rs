@@ -1,6 +1,6 @@
-use crate::states::MOTIVATIONAL_MSGS;
-use crate::{AppState, NoSaveAppState, SyncMode};
-use chrono::Local;
+use crate::states::{generate_date_range, LogEntry, MOTIVATIONAL_MSGS};
+use crate::{AppState, NoSaveAppState, SyncCoordinator, SyncMode};
+use chrono::{Datelike, Local, NaiveDate};
 use dioxus::prelude::*;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
@@ -18,9 +18,8 @@ fn random_motivational_msg() -> String {
 #[component]
 pub fn ActionLog() -> Element {
     let no_save_app_state = use_context::<NoSaveAppState>();
-    let mut sync_msg = no_save_app_state.sync_msg;
-    let mut sync_mode = no_save_app_state.sync_mode;
-    let mut fire_push = use_signal(|| false);
+    let sync_mode = no_save_app_state.sync_mode;
+    let sync_coordinator = use_context::<SyncCoordinator>();
 
     let mut app_state = use_context::<AppState>();
 
@@ -57,38 +56,47 @@ pub fn ActionLog() -> Element {
         }
     });
 
+    let mut note = use_signal(|| "".to_string());
+
+    // Only effective days of the task up to today can be logged, latest first
+    let valid_dates = use_memo(move || {
+        let today = Local::now().date_naive();
+        selected_task_id()
+            .and_then(|id| (app_state.tasks)().and_then(|tasks| tasks.get(&id).cloned()))
+            .map(|task| {
+                let mut dates =
+                    generate_date_range(task.start, task.end.min(today), task.effective_dow);
+                dates.reverse();
+                dates
+            })
+            .unwrap_or_default()
+    });
+
+    let mut selected_date = use_signal::<Option<NaiveDate>>(|| None);
+    let mut dates_of_task = use_signal::<Option<i64>>(|| None);
+
+    // A backdated day stays picked across submits and pulls, as long as it can still be logged on
+    use_effect(move || {
+        let dates = valid_dates();
+        let task_id = selected_task_id();
+
+        let still_valid = *dates_of_task.peek() == task_id
+            && selected_date.peek().is_some_and(|date| dates.contains(&date));
+
+        if !still_valid {
+            selected_date.set(dates.first().copied());
+        }
+        dates_of_task.set(task_id);
+    });
+
     let enable_submit = use_memo(move || {
         selected_task_id().is_some()
+            && selected_date().is_some()
             && count_done() > 0.0_f32
             && sync_mode() != SyncMode::Pushing
             && sync_mode() != SyncMode::Pulling
     });
 
-    use_effect(move || {
-        if fire_push() {
-            spawn({
-                sync_mode.set(SyncMode::Pushing);
-                async move {
-                    let mut app_state = use_context::<AppState>();
-                    match app_state.push().await {
-                        Ok(_) => {
-                            sync_msg
-                                .set("✅ Automatic push was successful after logging!".to_string());
-                            sync_mode.set(SyncMode::InSync);
-                        }
-                        Err(e) => {
-                            sync_msg.set(format!(
-                                "⚠️ Automatic push failed after creating logging: {}",
-                                e
-                            ));
-                            sync_mode.set(SyncMode::NotSynced);
-                        }
-                    }
-                }
-            });
-        }
-    });
-
     rsx! {
         if has_tasks() {
             div {
@@ -170,6 +178,61 @@ pub fn ActionLog() -> Element {
                     }
                 }
 
+                div {
+                    label {
+                        class: "block text-sm font-medium text-gray-700 mb-1",
+                        "On which day?"
+                    }
+
+                    if valid_dates().is_empty() {
+                        p {
+                            class: "text-sm text-gray-500",
+                            "This task has no effective day up to today to log on."
+                        }
+                    } else {
+                        select {
+                            class: "block w-full border border-gray-300 rounded-md shadow-sm p-2",
+                            onchange: move |e| {
+                                if let Ok(date) = NaiveDate::parse_from_str(&e.value(), "%Y-%m-%d") {
+                                    selected_date.set(Some(date));
+                                }
+                            },
+
+                            {
+                                let today = Local::now().date_naive();
+                                valid_dates().into_iter().map(move |date| rsx! {
+                                    option {
+                                        value: "{date}",
+                                        selected: selected_date() == Some(date),
+                                        {
+                                            if date == today {
+                                                format!("{} [{}] (today)", date, date.weekday())
+                                            } else {
+                                                format!("{} [{}]", date, date.weekday())
+                                            }
+                                        }
+                                    }
+                                })
+                            }
+                        }
+                    }
+                }
+
+                div {
+                    label {
+                        class: "block text-sm font-medium text-gray-700 mb-1",
+                        "Note (optional):"
+                    }
+
+                    input {
+                        r#type: "text",
+                        class: "block w-full border border-gray-300 rounded-md shadow-sm p-2",
+                        placeholder: "e.g. Chapter 3 on the train",
+                        value: "{note}",
+                        oninput: move |e| note.set(e.value())
+                    }
+                }
+
                 div {
                     class: "pt-4 flex items-center gap-x-4",
                     button {
@@ -183,16 +246,19 @@ pub fn ActionLog() -> Element {
                             }
                         ),
                         onclick: move |_| {
-                            if let Some(id) = selected_task_id() {
+                            if let (Some(id), Some(date)) = (selected_task_id(), selected_date()) {
                                 let mut tasks_write = app_state.tasks.write();
                                 if let Some(tasks_mut) = tasks_write.as_mut() {
                                     if let Some(task) = tasks_mut.get_mut(&id) {
-                                        task.count_accum += count_done();
+                                        let note_str = note().trim().to_string();
+                                        let note_opt = if note_str.is_empty() { None } else { Some(note_str) };
+                                        task.logs.push(LogEntry::new(date, count_done(), note_opt));
+                                        note.set("".to_string());
 
                                         // Use motivational messages in production
                                         submit_return_msg.set(random_motivational_msg());
 
-                                        fire_push.set(true);
+                                        sync_coordinator.request_push("Logged progress");
                                     }
                                 }
                             }
@@ -0,0 +1,238 @@
+use crate::states::{activity_by_day, entries_on, AppState, MyTask};
+use crate::Route;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use dioxus::prelude::*;
+use super::css_preset::*;
+
+const N_WEEKS: i64 = 53;
+const CELL_SIZE: i64 = 12;
+const CELL_STEP: i64 = 15;
+const LEFT_MARGIN: i64 = 30;
+const TOP_MARGIN: i64 = 16;
+
+// GitHub's contribution colours, from nothing logged to every planned task done
+const COLOR_NOTHING_PLANNED: &str = "#ffffff";
+const COLOR_SCALE: [&str; 5] = ["#ebedf0", "#9be9a8", "#40c463", "#30a14e", "#216e39"];
+
+fn cell_color(fraction: Option<f32>) -> &'static str {
+    match fraction {
+        None => COLOR_NOTHING_PLANNED,
+        Some(f) if f <= 0.0 => COLOR_SCALE[0],
+        Some(f) if f < 0.34 => COLOR_SCALE[1],
+        Some(f) if f < 0.67 => COLOR_SCALE[2],
+        Some(f) if f < 1.0 => COLOR_SCALE[3],
+        Some(_) => COLOR_SCALE[4],
+    }
+}
+
+#[component]
+pub fn ActivityCalendar() -> Element {
+    let app_state = use_context::<AppState>();
+    let navigator = use_navigator();
+
+    let today = Local::now().date_naive();
+    let mut selected = use_signal(|| today);
+
+    // Weeks start on Monday, and the last column is the current week
+    let first_day = today - Duration::weeks(N_WEEKS - 1);
+    let first_day = first_day - Duration::days(first_day.weekday().num_days_from_monday() as i64);
+
+    let mut tasks: Vec<MyTask> = (app_state.tasks)()
+        .unwrap_or_default()
+        .into_values()
+        .filter(|task| !task.archive)
+        .collect();
+    tasks.sort_by_key(|task| task.id);
+
+    let days = activity_by_day(&tasks, first_day, today);
+    let selected_day = days.iter().find(|day| day.date == selected()).copied();
+    let selected_entries = entries_on(&tasks, selected());
+
+    let width = LEFT_MARGIN + N_WEEKS * CELL_STEP;
+    let height = TOP_MARGIN + 7 * CELL_STEP;
+
+    let position = move |date: NaiveDate| {
+        let column = (date - first_day).num_days() / 7;
+        let row = date.weekday().num_days_from_monday() as i64;
+        (LEFT_MARGIN + column * CELL_STEP, TOP_MARGIN + row * CELL_STEP)
+    };
+
+    // A month's name above the first week that starts in it
+    let month_labels: Vec<(i64, String)> = (0..N_WEEKS)
+        .map(|column| first_day + Duration::weeks(column))
+        .filter(|monday| monday.day() <= 7)
+        .map(|monday| (position(monday).0, monday.format("%b").to_string()))
+        .collect();
+
+    rsx! {
+        div {
+            class: CSS_CONTENT_CARD,
+
+            h2 {
+                class: "text-xl font-bold text-center",
+                "📅 Activity Calendar"
+            }
+
+            p {
+                class: "text-center text-sm text-gray-600",
+                "How much of each day's planned work was logged, over all active tasks. Click a day to see what was logged on it."
+            }
+
+            div {
+                class: "overflow-x-auto",
+                svg {
+                    width: "{width}",
+                    height: "{height}",
+                    view_box: "0 0 {width} {height}",
+
+                    {
+                        month_labels.into_iter().map(|(x, month)| rsx! {
+                            text {
+                                key: "{x}",
+                                x: "{x}",
+                                y: "{TOP_MARGIN - 5}",
+                                font_size: "10",
+                                fill: "#6b7280",
+                                "{month}"
+                            }
+                        })
+                    }
+
+                    {
+                        [(0, "Mon"), (2, "Wed"), (4, "Fri")].into_iter().map(|(row, weekday)| rsx! {
+                            text {
+                                key: "{weekday}",
+                                x: "0",
+                                y: "{TOP_MARGIN + row * CELL_STEP + CELL_SIZE - 2}",
+                                font_size: "10",
+                                fill: "#6b7280",
+                                "{weekday}"
+                            }
+                        })
+                    }
+
+                    {
+                        days.iter().map(|day| {
+                            let date = day.date;
+                            let (x, y) = position(date);
+                            let is_selected = selected() == date;
+
+                            rsx! {
+                                rect {
+                                    key: "{date}",
+                                    x: "{x}",
+                                    y: "{y}",
+                                    width: "{CELL_SIZE}",
+                                    height: "{CELL_SIZE}",
+                                    rx: "2",
+                                    fill: cell_color(day.fraction()),
+                                    stroke: if is_selected { "#1e3a8a" } else { "#d1d5db" },
+                                    stroke_width: if is_selected { "2" } else { "0.5" },
+                                    class: "cursor-pointer",
+                                    onclick: move |_| selected.set(date),
+                                }
+                            }
+                        })
+                    }
+                }
+            }
+
+            div {
+                class: "flex items-center justify-end gap-1 text-xs text-gray-500",
+                span { "Less" }
+                {
+                    COLOR_SCALE.iter().map(|color| rsx! {
+                        span {
+                            key: "{color}",
+                            class: "inline-block w-3 h-3 rounded-sm border border-gray-300",
+                            style: "background-color: {color}",
+                        }
+                    })
+                }
+                span { "More" }
+            }
+
+            div {
+                class: "space-y-2",
+
+                h3 {
+                    class: "text-lg font-semibold text-blue-800",
+                    { format!("{} ({})", selected(), selected().weekday()) }
+                }
+
+                p {
+                    class: "text-sm text-gray-600",
+                    {
+                        match selected_day.and_then(|day| day.fraction().map(|fraction| (day, fraction))) {
+                            Some((day, fraction)) => format!(
+                                "{:.1} of {} planned task(s) done ({:.0}%)",
+                                day.done,
+                                day.n_planned,
+                                fraction * 100.0
+                            ),
+                            None => "Nothing was planned for this day.".to_string(),
+                        }
+                    }
+                }
+
+                if selected_entries.is_empty() {
+                    p {
+                        class: "text-sm text-gray-500",
+                        "Nothing was logged on this day."
+                    }
+                }
+
+                {
+                    selected_entries.iter().map(|(task, entries)| {
+                        let id = task.id;
+
+                        rsx! {
+                            div {
+                                key: "{id}",
+                                class: "p-2 border border-gray-200 rounded-lg space-y-1",
+
+                                div {
+                                    class: "flex items-center justify-between",
+                                    p {
+                                        class: "font-semibold text-gray-800",
+                                        { task.label() }
+                                    }
+                                    button {
+                                        class: "text-sm text-blue-700 hover:underline cursor-pointer",
+                                        onclick: move |_| {
+                                            navigator.push(Route::Director { pagename: format!("ActionHistory/{}", id) });
+                                        },
+                                        "🕘 History"
+                                    }
+                                }
+
+                                {
+                                    entries.iter().map(|entry| rsx! {
+                                        p {
+                                            key: "{entry.id}",
+                                            class: "text-sm text-gray-700",
+                                            {
+                                                format!(
+                                                    "+{} {} at {}",
+                                                    entry.amount,
+                                                    task.unit,
+                                                    entry.timestamp.format("%H:%M")
+                                                )
+                                            }
+                                            if let Some(note) = &entry.note {
+                                                span {
+                                                    class: "text-gray-500",
+                                                    " 📝 {note}"
+                                                }
+                                            }
+                                        }
+                                    })
+                                }
+                            }
+                        }
+                    })
+                }
+            }
+        }
+    }
+}
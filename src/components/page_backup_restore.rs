@@ -0,0 +1,132 @@
+use crate::states::{backup_now, list_backups, load_backup};
+use crate::Route;
+use crate::{AppState, SyncCoordinator};
+use dioxus::prelude::*;
+use super::css_preset::*;
+
+#[component]
+pub fn BackupRestore() -> Element {
+    let mut app_state = use_context::<AppState>();
+    let sync_coordinator = use_context::<SyncCoordinator>();
+    let navigator = use_navigator();
+
+    let mut backups = use_signal(list_backups);
+    let mut pending_restore = use_signal::<Option<String>>(|| None);
+    let mut restore_return_msg = use_signal(|| "".to_string());
+
+    let mut restore_backup = move |id: &str| match load_backup(id) {
+        Ok(state) => {
+            backup_now();
+
+            let state = AppState::from(state);
+
+            // Against the current base the restore is a local change that the next push keeps.
+            // Its own base would make the next sync take the synced version of every task back.
+            let same_target = (state.sync_backend)() == (app_state.sync_backend)()
+                && (state.gist_config)() == (app_state.gist_config)()
+                && (state.webdav_config)() == (app_state.webdav_config)()
+                && (state.s3_config)() == (app_state.s3_config)()
+                && (state.folder_config)() == (app_state.folder_config)();
+            if !same_target {
+                app_state.sync_base.set(None);
+            }
+
+            app_state.tasks.set((state.tasks)());
+            app_state.sync_backend.set((state.sync_backend)());
+            app_state.gist_config.set((state.gist_config)());
+            app_state.webdav_config.set((state.webdav_config)());
+            app_state.s3_config.set((state.s3_config)());
+            app_state.folder_config.set((state.folder_config)());
+            app_state.sync_passphrase.set((state.sync_passphrase)());
+            app_state.quarantine.set((state.quarantine)());
+
+            sync_coordinator.request_push("Restored a backup");
+            restore_return_msg.set("✅ Restored the backup".to_string());
+            backups.set(list_backups());
+        }
+        Err(e) => {
+            restore_return_msg.set(format!("❌ Failed to restore the backup: {}", e));
+        }
+    };
+
+    rsx! {
+        div {
+            class: CSS_CONTENT_CARD,
+
+            h2 { class: "text-xl font-bold", "🗄 Restore from backup" }
+            p {
+                class: "text-sm text-gray-500",
+                "Restoring replaces everything on this device. What you have now is backed up first, so a restore can be undone."
+            }
+
+            if backups().is_empty() {
+                p {
+                    class: "text-center text-gray-500",
+                    "There are no backups yet."
+                }
+            } else {
+                div {
+                    class: "grid grid-cols-1 gap-2",
+                    {
+                        backups().into_iter().map(|backup| {
+                            let id = backup.id.clone();
+                            let is_pending = pending_restore() == Some(backup.id.clone());
+
+                            rsx! {
+                                div {
+                                    key: "{backup.id}",
+                                    class: "flex items-center justify-between p-2 border border-gray-200 rounded-lg",
+
+                                    div {
+                                        p { class: "font-semibold", { backup.created.format("%Y-%m-%d %H:%M:%S").to_string() } }
+                                        p {
+                                            class: "text-sm text-gray-600",
+                                            {
+                                                match backup.task_count {
+                                                    Some(count) => format!("{} task(s)", count),
+                                                    None => "Unreadable".to_string(),
+                                                }
+                                            }
+                                        }
+                                    }
+
+                                    button {
+                                        disabled: backup.task_count.is_none(),
+                                        class: "bg-amber-600 hover:bg-amber-700 text-white font-medium py-2 px-4 rounded-lg transition-all cursor-pointer",
+                                        onclick: move |_| {
+                                            if pending_restore() != Some(id.clone()) {
+                                                pending_restore.set(Some(id.clone()));
+                                            } else {
+                                                pending_restore.set(None);
+                                                restore_backup(&id);
+                                            }
+                                        },
+                                        if is_pending {"Confirm Restoring"} else {"Restore"}
+                                    }
+                                }
+                            }
+                        })
+                    }
+                }
+            }
+
+            if !restore_return_msg().is_empty() {
+                p {
+                    class: "text-center text-sm text-gray-700",
+                    "{restore_return_msg}"
+                }
+            }
+
+            div {
+                class: "flex justify-center",
+                button {
+                    class: "bg-gray-100 text-gray-800 font-medium py-2 px-4 rounded-lg transition-all hover:ring hover:ring-gray-300 hover:ring-offset-2 cursor-pointer",
+                    onclick: move |_| {
+                        navigator.push(Route::Director { pagename: "Setting".to_string() });
+                    },
+                    "⬅️ Back to Settings",
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use crate::states::{backup_now, GistRevision, MyTask};
+use crate::Route;
+use crate::{AppState, SyncCoordinator};
+use chrono::{DateTime, Local};
+use dioxus::prelude::*;
+use std::collections::HashSet;
+use super::css_preset::*;
+
+#[derive(Debug, Clone, PartialEq)]
+struct RevisionPreview {
+    version: String,
+    tasks: Vec<MyTask>,
+    n_unreadable: usize,
+}
+
+fn revision_date(revision: &GistRevision) -> String {
+    DateTime::parse_from_rfc3339(&revision.committed_at)
+        .map(|date| date.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|_| revision.committed_at.clone())
+}
+
+#[component]
+pub fn GistHistory() -> Element {
+    let sync_coordinator = use_context::<SyncCoordinator>();
+    let navigator = use_navigator();
+
+    let mut revisions = use_signal::<Option<Result<Vec<GistRevision>, String>>>(|| None);
+    let mut preview = use_signal::<Option<RevisionPreview>>(|| None);
+    let mut selected = use_signal(HashSet::<i64>::new);
+    let mut confirm_whole = use_signal(|| false);
+    let mut history_msg = use_signal(|| "".to_string());
+
+    use_hook(move || {
+        spawn(async move {
+            let app_state = consume_context::<AppState>();
+            revisions.set(Some((app_state.gist_config)().revisions().await));
+        });
+    });
+
+    let mut load_preview = move |version: String| {
+        history_msg.set("Fetching the revision ...".to_string());
+        spawn(async move {
+            let app_state = consume_context::<AppState>();
+            match app_state.gist_revision(&version).await {
+                Ok(state) => {
+                    let mut n_unreadable = state.quarantine.len();
+                    let mut tasks = vec![];
+
+                    for task in state.tasks.unwrap_or_default().iter() {
+                        match MyTask::try_from(task) {
+                            Ok(task) => tasks.push(task),
+                            Err(_) => n_unreadable += 1,
+                        }
+                    }
+                    tasks.sort_by_key(|task| task.id);
+
+                    selected.set(tasks.iter().map(|task| task.id).collect());
+                    confirm_whole.set(false);
+                    history_msg.set("".to_string());
+                    preview.set(Some(RevisionPreview {
+                        version,
+                        tasks,
+                        n_unreadable,
+                    }));
+                }
+                Err(e) => history_msg.set(e),
+            }
+        });
+    };
+
+    let mut restore = move |whole: bool| {
+        let Some(revision) = preview() else {
+            return;
+        };
+
+        let tasks: Vec<MyTask> = revision
+            .tasks
+            .into_iter()
+            .filter(|task| whole || selected().contains(&task.id))
+            .collect();
+        let n_restored = tasks.len();
+
+        backup_now();
+
+        let mut app_state = consume_context::<AppState>();
+        app_state.restore_tasks(tasks, whole);
+        sync_coordinator.request_push("Restored from the gist history");
+
+        confirm_whole.set(false);
+        history_msg.set(format!("✅ Restored {} task(s)", n_restored));
+    };
+
+    rsx! {
+        div {
+            class: CSS_CONTENT_CARD,
+
+            h2 { class: "text-xl font-bold", "🕰 Gist history" }
+            p {
+                class: "text-sm text-gray-500",
+                "Every push is kept as a revision of the gist. Preview one to bring back all of it or only some tasks. What you have now is backed up first."
+            }
+
+            match revisions() {
+                None => rsx! {
+                    p { class: "text-center text-gray-500", "Loading the history ..." }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-center text-red-700", { format!("❌ Failed to load the history: {}", e) } }
+                },
+                Some(Ok(revisions)) => rsx! {
+                    div {
+                        class: "grid grid-cols-1 gap-2 max-h-64 overflow-y-auto",
+                        {
+                            revisions.into_iter().map(|revision| {
+                                let version = revision.version.clone();
+                                let is_previewed = preview().is_some_and(|p| p.version == revision.version);
+
+                                rsx! {
+                                    div {
+                                        key: "{revision.version}",
+                                        class: if is_previewed {
+                                            "flex items-center justify-between p-2 border border-blue-300 bg-blue-50 rounded-lg"
+                                        } else {
+                                            "flex items-center justify-between p-2 border border-gray-200 rounded-lg"
+                                        },
+
+                                        div {
+                                            p { class: "font-semibold", { revision_date(&revision) } }
+                                            p {
+                                                class: "text-sm text-gray-600",
+                                                { format!("+{} / -{} lines", revision.additions, revision.deletions) }
+                                            }
+                                        }
+
+                                        button {
+                                            class: "bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded-lg transition-all cursor-pointer",
+                                            onclick: move |_| load_preview(version.clone()),
+                                            "Preview"
+                                        }
+                                    }
+                                }
+                            })
+                        }
+                    }
+                },
+            }
+
+            if let Some(revision) = preview() {
+                div {
+                    class: "space-y-2 border border-gray-200 rounded-lg p-4",
+                    p {
+                        class: "font-semibold",
+                        { format!("This revision has {} task(s)", revision.tasks.len()) }
+                    }
+
+                    if revision.n_unreadable > 0 {
+                        p {
+                            class: "text-sm text-amber-800",
+                            { format!("⚠️ {} task(s) in it could not be read and cannot be restored", revision.n_unreadable) }
+                        }
+                    }
+
+                    {
+                        revision.tasks.iter().map(|task| {
+                            let id = task.id;
+
+                            rsx! {
+                                label {
+                                    key: "{id}",
+                                    class: "flex items-center gap-2",
+                                    input {
+                                        r#type: "checkbox",
+                                        checked: selected().contains(&id),
+                                        onchange: move |_| {
+                                            let mut selected = selected.write();
+                                            if !selected.remove(&id) {
+                                                selected.insert(id);
+                                            }
+                                        },
+                                    }
+                                    span {
+                                        { format!("{} ({:.1} {} done{})", task.label(), task.count_accum(), task.unit, if task.archive { ", archived" } else { "" }) }
+                                    }
+                                }
+                            }
+                        })
+                    }
+
+                    div {
+                        class: "flex gap-4 justify-center",
+                        button {
+                            class: "bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded-lg transition-all cursor-pointer",
+                            disabled: selected().is_empty(),
+                            onclick: move |_| restore(false),
+                            "Restore selected"
+                        }
+
+                        button {
+                            class: "bg-amber-600 hover:bg-amber-700 text-white font-medium py-2 px-4 rounded-lg transition-all cursor-pointer",
+                            onclick: move |_| {
+                                if confirm_whole() {
+                                    restore(true);
+                                } else {
+                                    confirm_whole.set(true);
+                                }
+                            },
+                            if confirm_whole() {"Confirm replacing all tasks"} else {"Restore whole"}
+                        }
+                    }
+                }
+            }
+
+            if !history_msg().is_empty() {
+                p {
+                    class: "text-center text-sm text-gray-700",
+                    "{history_msg}"
+                }
+            }
+
+            div {
+                class: "flex justify-center",
+                button {
+                    class: "bg-gray-100 text-gray-800 font-medium py-2 px-4 rounded-lg transition-all hover:ring hover:ring-gray-300 hover:ring-offset-2 cursor-pointer",
+                    onclick: move |_| {
+                        navigator.push(Route::Director { pagename: "Setting".to_string() });
+                    },
+                    "⬅️ Back to Settings",
+                }
+            }
+        }
+    }
+}
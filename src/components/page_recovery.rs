@@ -0,0 +1,59 @@
+use crate::states::set_aside_damaged;
+use crate::NoSaveAppState;
+use dioxus::prelude::*;
+use super::css_preset::*;
+
+#[component]
+pub fn Recovery() -> Element {
+    let no_save_app_state = use_context::<NoSaveAppState>();
+    let mut load_error = no_save_app_state.load_error;
+
+    let mut recovery_msg = use_signal(|| "".to_string());
+
+    rsx! {
+        div {
+            class: CSS_CONTENT_CARD,
+
+            h2 { class: "text-xl font-bold text-red-700", "🩹 Your saved data could not be read" }
+
+            p {
+                class: "text-gray-700",
+                "Nothing has been changed or overwritten yet. The problem was:"
+            }
+
+            p {
+                class: "text-sm font-mono bg-gray-100 rounded p-2 break-all",
+                { load_error().unwrap_or_default() }
+            }
+
+            p {
+                class: "text-gray-700",
+                "You can keep a copy of the damaged data and start fresh. If sync is set up, your tasks can be pulled again from the Sync Settings afterwards."
+            }
+
+            div {
+                class: "flex justify-center",
+                button {
+                    class: "font-semibold py-2 px-4 rounded bg-red-600 hover:bg-red-700 text-white cursor-pointer transition-colors duration-300",
+                    onclick: move |_| {
+                        match set_aside_damaged() {
+                            Ok(location) => {
+                                log::info!("Damaged data was kept at {}", location);
+                                load_error.set(None);
+                            }
+                            Err(e) => recovery_msg.set(format!("❌ {}", e)),
+                        }
+                    },
+                    "Keep a Copy and Start Fresh"
+                }
+            }
+
+            if !recovery_msg().is_empty() {
+                p {
+                    class: "text-center text-sm text-gray-700",
+                    "{recovery_msg}"
+                }
+            }
+        }
+    }
+}
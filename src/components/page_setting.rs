@@ -1,60 +1,55 @@
-use crate::states::generate_qr_data_url;
-use crate::{AppState, NoSaveAppState, SerializableState, SyncMode};
+use crate::states::{MyTask, SyncBackendKind, SyncConflict};
+use crate::{AppState, NoSaveAppState, Route, SerializableState, SyncCoordinator, SyncMode};
 use dioxus::prelude::*;
+use strum::IntoEnumIterator;
 use super::css_preset::*;
+use super::setting_folder::FolderSetting;
+use super::setting_gist::GistSetting;
+use super::setting_pairing::PairingSetting;
+use super::setting_s3::S3Setting;
+use super::setting_webdav::WebDavSetting;
 
 const APPKEY: &str = "OBFUSCATION";
 
+fn conflict_label(conflict: &SyncConflict) -> String {
+    match conflict.local.as_ref().or(conflict.remote.as_ref()) {
+        Some(task) => task.label(),
+        None => format!("Task {}", conflict.task_id),
+    }
+}
+
+fn conflict_side_summary(task: &Option<MyTask>) -> String {
+    match task {
+        Some(task) => format!(
+            "{:.1} {} done, {} entries, from {} to {}{}",
+            task.count_accum(),
+            task.unit,
+            task.logs.len(),
+            task.start,
+            task.end,
+            if task.archive { ", archived" } else { "" }
+        ),
+        None => "removed".to_string(),
+    }
+}
+
 #[component]
 pub fn Setting() -> Element {
     let mut app_state_push = use_context::<AppState>();
     let mut app_state = use_context::<AppState>();
+    let mut sync_passphrase = app_state.sync_passphrase;
+    let mut backup_count = app_state.backup_count;
+    let mut pace_warning_multiple = app_state.pace_warning_multiple;
+    let navigator = use_navigator();
 
     let no_save_app_state = use_context::<NoSaveAppState>();
-    let mut sync_msg = no_save_app_state.sync_msg;
     let mut sync_mode = no_save_app_state.sync_mode;
+    let mut sync_conflicts = no_save_app_state.sync_conflicts;
+    let sync_log = no_save_app_state.sync_log;
+    let sync_coordinator = use_context::<SyncCoordinator>();
 
-    let gist_id = app_state.gist_id;
-
-    let mut gist_id_qr_ready = use_memo(move || {
-        if let Some(gist_id) = gist_id() {
-            if !gist_id.is_empty() {
-                return true;
-            }
-        }
-
-        false
-    });
-
-    let mut gist_id_qr_show = use_signal(|| false);
-
-    let github_pat = app_state.github_pat;
-
-    let mut pat_qr_ready = use_memo(move || {
-        if let Some(github_pat) = github_pat() {
-            if !github_pat.is_empty() {
-                return true;
-            }
-        }
-
-        false
-    });
-
-    let mut pat_qr_show = use_signal(|| false);
-
-    let clickable = use_memo(move || {
-        if let Some(github_pat) = (app_state.github_pat)() {
-            if let Some(gist_id) = (app_state.gist_id)() {
-                if let Some(gist_file_name) = (app_state.gist_file_name)() {
-                    if !github_pat.is_empty() && !gist_id.is_empty() && !gist_file_name.is_empty() {
-                        return true;
-                    }
-                }
-            }
-        }
-
-        false
-    });
+    let app_state_for_check = use_context::<AppState>();
+    let clickable = use_memo(move || app_state_for_check.sync_configured());
 
     use_effect(move || {
         if !clickable() {
@@ -81,141 +76,54 @@ pub fn Setting() -> Element {
             p { class: "text-sm text-gray-500", "⚠️ Sync functionality is currently in beta. Use with caution." }
 
             div {
-                label { "GitHub Gist ID" }
-                input {
+                label { "Sync with" }
+                select {
                     class: "w-full border rounded p-2",
-                    value: app_state.gist_id,
-                    oninput: move |evt| {
-                        let user_input = evt.value();
-                        if user_input.is_empty() {
-                            (app_state.gist_id).set(None);
-                        } else {
-                            (app_state.gist_id).set(Some(user_input));
+                    onchange: move |evt| {
+                        if let Some(kind) = SyncBackendKind::iter().find(|kind| format!("{:?}", kind) == evt.value()) {
+                            // The sync base belongs to the previous backend, so the next sync merges in full
+                            app_state.sync_backend.set(kind);
+                            app_state.sync_base.set(None);
                         }
                     },
-                }
-            }
-
-            if gist_id_qr_ready() {
-                div {
-                    class: "flex justify-center",
-                    button {
-                        class: "font-semibold py-2 px-4 rounded transition-colors duration-300 bg-blue-600 hover:bg-blue-700 text-white cursor-pointer",
-                        onclick: move |_| gist_id_qr_show.set(!gist_id_qr_show()),
-                        "Show Gist ID QR Code"
-                    }
-                }
-
-                if gist_id_qr_show() {
-                    div {
-                        class: "fixed inset-0 flex items-center justify-center bg-white/90 z-50",
-                        onclick: move |_| gist_id_qr_show.set(false),
-
-                        div {
-                            class: "bg-white p-6 rounded shadow-lg z-60",
-                            onclick: |_| {},
-
-                            h3 {
-                                class: "text-lg font-semibold mb-2",
-                                "Scan to import code:"
-                            }
-
-                            {
-                                if let Some(url) = generate_qr_data_url((app_state.gist_id)().unwrap()) {
-                                    rsx! {
-                                        img {
-                                            src: url,
-                                            alt: "QR code",
-                                            class: "w-48 h-48 border mx-auto"
-                                        }
-                                    }
-                                } else {
-                                    rsx! {
-                                        h3 { "Not able to generate a QR code ..." }
-                                    }
-                                }
+                    {
+                        SyncBackendKind::iter().filter(|kind| kind.is_available()).map(|kind| rsx! {
+                            option {
+                                value: "{kind:?}",
+                                selected: (app_state.sync_backend)() == kind,
+                                "{kind}"
                             }
-                        }
+                        })
                     }
-
                 }
             }
 
-            div {
-                label { "Gist File Name" }
-                input {
-                    class: "w-full border rounded p-2",
-                    value: app_state.gist_file_name,
-                    oninput: move |evt| {
-                        let user_input = evt.value();
-                        if user_input.is_empty() {
-                            (app_state.gist_file_name).set(None);
-                        } else {
-                            (app_state.gist_file_name).set(Some(user_input));
-                        }
-                    },
-                }
+            match (app_state.sync_backend)() {
+                SyncBackendKind::Gist => rsx! { GistSetting {} },
+                SyncBackendKind::WebDav => rsx! { WebDavSetting {} },
+                SyncBackendKind::S3 => rsx! { S3Setting {} },
+                SyncBackendKind::Folder => rsx! { FolderSetting {} },
             }
 
             div {
-                label { "GitHub Personal Access Token (Classic)" }
+                label { "Sync Passphrase (optional)" }
                 input {
                     r#type: "password",
                     class: "w-full border rounded p-2",
-                    value: app_state.github_pat,
+                    autocomplete: "off",
+                    value: sync_passphrase().unwrap_or_default(),
                     oninput: move |evt| {
                         let user_input = evt.value();
                         if user_input.is_empty() {
-                            (app_state.github_pat).set(None)
+                            sync_passphrase.set(None);
                         } else {
-                            (app_state.github_pat).set(Some(user_input))
+                            sync_passphrase.set(Some(user_input));
                         }
                     },
                 }
-            }
-
-            if pat_qr_ready() {
-                div {
-                    class: "flex justify-center",
-                    button {
-                        class: "font-semibold py-2 px-4 rounded transition-colors duration-300 bg-blue-600 hover:bg-blue-700 text-white cursor-pointer",
-                        onclick: move |_| pat_qr_show.set(!pat_qr_show()),
-                        "Show GitHub PAT QR Code"
-                    }
-                }
-
-                if pat_qr_show() {
-                    div {
-                        class: "fixed inset-0 flex items-center justify-center bg-white/90 z-50",
-                        onclick: move |_| pat_qr_show.set(false),
-
-                        div {
-                            class: "bg-white p-6 rounded shadow-lg z-60",
-                            onclick: |_| {},
-
-                            h3 {
-                                class: "text-lg font-semibold mb-2",
-                                "Scan to import code:"
-                            }
-
-                            {
-                                if let Some(url) = generate_qr_data_url((app_state.github_pat)().unwrap()) {
-                                    rsx! {
-                                        img {
-                                            src: url,
-                                            alt: "QR code",
-                                            class: "w-48 h-48 border mx-auto"
-                                        }
-                                    }
-                                } else {
-                                    rsx! {
-                                        h3 { "Not able to generate a QR code ..." }
-                                    }
-                                }
-                            }
-                        }
-                    }
-
+                p {
+                    class: "text-sm text-gray-500",
+                    "When set, synced data is end-to-end encrypted. Use the same passphrase on every device, it is never uploaded."
                 }
             }
 
@@ -236,22 +144,7 @@ pub fn Setting() -> Element {
                     disabled: !clickable(),
                     onclick: move |_| {
                         if clickable() {
-                            spawn({
-                                sync_mode.set(SyncMode::Pushing);
-                                async move {
-                                    let mut app_state = use_context::<AppState>();
-                                    match app_state.push().await {
-                                        Ok(_) => {
-                                            sync_msg.set("✅ Manual push was successful!".to_string());
-                                            sync_mode.set(SyncMode::InSync);
-                                        },
-                                        Err(e) => {
-                                            sync_msg.set(format!("⚠️ Manual push failed: {}", e));
-                                            sync_mode.set(SyncMode::NotSynced);
-                                        },
-                                    }
-                                }
-                            });
+                            sync_coordinator.push_now("Manual push");
                         }
                     },
                     "Push: upload local data"
@@ -271,39 +164,163 @@ pub fn Setting() -> Element {
                     disabled: !clickable(),
                     onclick: move |_| {
                         if clickable() {
-                            spawn({
-                                sync_mode.set(SyncMode::Pulling);
-                                async move {
-                                    let mut app_state = use_context::<AppState>();
-                                    match app_state.pull().await {
-                                        Ok(_) => {
-                                            sync_msg.set("✅ Manual pull was successful!".to_string());
-                                            sync_mode.set(SyncMode::InSync);
-                                        },
-                                        Err(e) => {
-                                            sync_msg.set(format!("⚠️ Manual pull failed: {}", e));
-                                            sync_mode.set(SyncMode::NotSynced);
-                                        },
-                                    }
-                                }
-                            });
+                            sync_coordinator.request_pull("Manual pull");
                         }
                     },
                     "Pull: restore from cloud data"
                 }
             }
 
+            if !sync_conflicts().is_empty() {
+                div {
+                    class: "border border-red-300 bg-red-50 rounded-lg p-4 space-y-2",
+                    h2 { class: "text-lg font-bold text-red-700", "⚠️ Sync conflicts" }
+                    p {
+                        class: "text-sm text-gray-600",
+                        "These tasks were changed on this device and elsewhere in ways that could not be combined. This device's version is kept until you choose otherwise."
+                    }
+
+                    {
+                        sync_conflicts().into_iter().map(|conflict| {
+                            let task_id = conflict.task_id;
+                            let local = conflict.local.clone();
+                            let remote = conflict.remote.clone();
+
+                            rsx! {
+                                div {
+                                    key: "{task_id}",
+                                    class: "bg-white border border-gray-200 rounded-lg p-2 space-y-1",
+                                    p { class: "font-semibold", "{conflict_label(&conflict)}" }
+                                    p { class: "text-sm text-gray-700", "📱 This device: {conflict_side_summary(&conflict.local)}" }
+                                    p { class: "text-sm text-gray-700", "☁️ Synced: {conflict_side_summary(&conflict.remote)}" }
+
+                                    div {
+                                        class: "flex gap-4 justify-center",
+                                        button {
+                                            class: "font-semibold py-1 px-3 rounded bg-blue-600 hover:bg-blue-700 text-white cursor-pointer",
+                                            onclick: move |_| {
+                                                let mut tasks_guard = app_state.tasks.write();
+                                                let tasks = tasks_guard.get_or_insert_with(Default::default);
+                                                match local.clone() {
+                                                    Some(task) => tasks.insert(task_id, task),
+                                                    None => tasks.remove(&task_id),
+                                                };
+                                                sync_conflicts.write().retain(|c| c.task_id != task_id);
+                                                sync_coordinator.request_push("Resolved a conflict");
+                                            },
+                                            "Keep this device's"
+                                        }
+
+                                        button {
+                                            class: "font-semibold py-1 px-3 rounded bg-cyan-600 hover:bg-cyan-700 text-white cursor-pointer",
+                                            onclick: move |_| {
+                                                let mut tasks_guard = app_state.tasks.write();
+                                                let tasks = tasks_guard.get_or_insert_with(Default::default);
+                                                match remote.clone() {
+                                                    Some(task) => tasks.insert(task_id, task),
+                                                    None => tasks.remove(&task_id),
+                                                };
+                                                sync_conflicts.write().retain(|c| c.task_id != task_id);
+                                                sync_coordinator.request_push("Resolved a conflict");
+                                            },
+                                            "Use synced"
+                                        }
+                                    }
+                                }
+                            }
+                        })
+                    }
+                }
+            }
+
             div {
                 h2 { class: "text-xl", { format!("Current sync status: {:?}", sync_mode()) } }
 
-                label { "Additional sync message:" }
-                textarea {
-                    class: "mt-1 block w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm focus:outline-none focus:ring-indigo-500 focus:border-indigo-500 sm:text-sm h-32",
-                    value: sync_msg,
-                    readonly: true,
+                label { "Recent syncs:" }
+                div {
+                    class: "mt-1 w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm sm:text-sm h-32 overflow-y-auto",
+                    if sync_log().is_empty() {
+                        p { class: "text-gray-500", "Nothing has been synced yet." }
+                    }
+                    {
+                        sync_log().into_iter().rev().map(|entry| {
+                            let outcome = match &entry.result {
+                                Ok(0) => "✅".to_string(),
+                                Ok(n) => format!("✅ {} new conflict(s)", n),
+                                Err(e) => format!("⚠️ {}", e),
+                            };
+
+                            rsx! {
+                                p {
+                                    class: "text-gray-700",
+                                    {
+                                        format!(
+                                            "{} {:?} ({}) {}",
+                                            entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                                            entry.action,
+                                            entry.reasons.join(", "),
+                                            outcome
+                                        )
+                                    }
+                                }
+                            }
+                        })
+                    }
+                }
+            }
+
+            PairingSetting {}
+
+            div {
+                class: "space-y-2",
+                h2 { class: "text-xl font-bold", "Forecast" }
+
+                label { "Warn when a task needs more than this many times its daily amount to finish in time" }
+                input {
+                    r#type: "number",
+                    min: "1",
+                    step: "0.1",
+                    class: "w-full border rounded p-2",
+                    value: "{pace_warning_multiple}",
+                    oninput: move |evt| {
+                        if let Ok(multiple) = evt.value().parse::<f32>() {
+                            if multiple >= 1.0 {
+                                pace_warning_multiple.set(multiple);
+                            }
+                        }
+                    },
                 }
             }
 
+            div {
+                class: "space-y-2",
+                h2 { class: "text-xl font-bold", "Local Backups" }
+
+                label { "Number of backups to keep (at most one per hour)" }
+                input {
+                    r#type: "number",
+                    min: "0",
+                    step: "1",
+                    class: "w-full border rounded p-2",
+                    value: "{backup_count}",
+                    oninput: move |evt| {
+                        if let Ok(count) = evt.value().parse::<usize>() {
+                            backup_count.set(count);
+                        }
+                    },
+                }
+
+                div {
+                    class: "flex justify-center",
+                    button {
+                        class: "bg-gray-100 text-gray-800 font-medium py-2 px-4 rounded-lg transition-all hover:ring hover:ring-gray-300 hover:ring-offset-2 cursor-pointer",
+                        onclick: move |_| {
+                            navigator.push(Route::Director { pagename: "BackupRestore".to_string() });
+                        },
+                        "🗄 Restore from backup"
+                    }
+                }
+            }
         }
     }
 }
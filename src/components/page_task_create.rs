@@ -1,22 +1,11 @@
-use crate::states::MyTask;
-use crate::{AppState, NoSaveAppState, SyncMode};
-use chrono::{Datelike, Local, NaiveDate, Weekday};
+use crate::states::{calculate_completion_date, MyTask, ALL_WEEKDAYS};
+use crate::{AppState, NoSaveAppState, SyncCoordinator, SyncMode};
+use chrono::{Local, NaiveDate};
 use dioxus::prelude::*;
-use std::collections::HashSet;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 use super::css_preset::*;
 
-static ALL_WEEKDAYS: [Weekday; 7] = [
-    Weekday::Mon,
-    Weekday::Tue,
-    Weekday::Wed,
-    Weekday::Thu,
-    Weekday::Fri,
-    Weekday::Sat,
-    Weekday::Sun,
-];
-
 #[derive(PartialEq, Clone, Copy, EnumIter)]
 enum TaskCreationMode {
     SameActEveryDay,
@@ -36,51 +25,11 @@ impl std::fmt::Display for TaskCreationMode {
     }
 }
 
-pub fn calculate_completion_date(
-    n_tasks: usize,
-    start_date: NaiveDate,
-    effective_dow: Vec<Weekday>,
-) -> Option<NaiveDate> {
-    // If no tasks, it's already complete on the start date (or before)
-    if n_tasks == 0 {
-        return Some(start_date);
-    }
-    // If no effective days, tasks can never be completed
-    if effective_dow.is_empty() {
-        return None;
-    }
-
-    // Use a HashSet for efficient O(1) lookup of effective days
-    let effective_days_set: HashSet<Weekday> = effective_dow.iter().copied().collect();
-
-    let mut current_date = start_date;
-    let mut tasks_completed_count = 0;
-
-    // Loop until all tasks are completed
-    while tasks_completed_count < n_tasks {
-        // Check if the current day of the week is one of the effective days
-        if effective_days_set.contains(&current_date.weekday()) {
-            tasks_completed_count += 1;
-        }
-
-        // If tasks are all completed on the current_date, break before incrementing date
-        if tasks_completed_count == n_tasks {
-            break;
-        }
-
-        // Move to the next day
-        current_date += chrono::Duration::days(1);
-    }
-
-    Some(current_date)
-}
-
 #[component]
 pub fn TaskCreate() -> Element {
     let no_save_app_state = use_context::<NoSaveAppState>();
-    let mut sync_msg = no_save_app_state.sync_msg;
-    let mut sync_mode = no_save_app_state.sync_mode;
-    let mut fire_push = use_signal(|| false);
+    let sync_mode = no_save_app_state.sync_mode;
+    let sync_coordinator = use_context::<SyncCoordinator>();
 
     let mut app_state = use_context::<AppState>();
 
@@ -122,33 +71,6 @@ pub fn TaskCreate() -> Element {
         submit_return_msg.set("".to_string());
     });
 
-    use_effect(move || {
-        if fire_push() {
-            spawn({
-                sync_mode.set(SyncMode::Pushing);
-                async move {
-                    let mut app_state = use_context::<AppState>();
-                    match app_state.push().await {
-                        Ok(_) => {
-                            sync_msg.set(
-                                "✅ Automatic push was successful after creating a task!"
-                                    .to_string(),
-                            );
-                            sync_mode.set(SyncMode::InSync);
-                        }
-                        Err(e) => {
-                            sync_msg.set(format!(
-                                "⚠️ Automatic push failed after creating a task: {}",
-                                e
-                            ));
-                            sync_mode.set(SyncMode::NotSynced);
-                        }
-                    }
-                }
-            });
-        }
-    });
-
     rsx! {
         div {
             class: CSS_CONTENT_CARD,
@@ -324,7 +246,7 @@ pub fn TaskCreate() -> Element {
                                         action: action().clone(),
                                         count_per_day: count_per_day(),
                                         unit: unit().clone(),
-                                        count_accum: 0.0_f32,
+                                        logs: vec![],
                                         start: start_date,
                                         end: end_date,
                                         effective_dow: selected_dow(),
@@ -344,7 +266,7 @@ pub fn TaskCreate() -> Element {
 
                                     let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
                                     submit_return_msg.set(format!("✅ Created a task ({})", now));
-                                    fire_push.set(true);
+                                    sync_coordinator.request_push("Created a task");
                                 } else {
                                     submit_return_msg.set("❌ Task creation failed! Please check dates!".to_string());
                                 }
@@ -469,7 +391,7 @@ pub fn TaskCreate() -> Element {
                                             action: "Complete".to_string(),
                                             count_per_day: 1.0_f32,
                                             unit: "line of daily activities".to_string(),
-                                            count_accum: 0.0_f32,
+                                            logs: vec![],
                                             start: start_date,
                                             end: end_date,
                                             effective_dow: selected_dow(),
@@ -489,7 +411,7 @@ pub fn TaskCreate() -> Element {
 
                                         let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
                                         submit_return_msg.set(format!("✅ Created a task ({})", now));
-                                        fire_push.set(true);
+                                        sync_coordinator.request_push("Created a task");
                                     } else {
                                         submit_return_msg.set("❌ Task creation failed! Please check dates!".to_string());
                                     }
@@ -0,0 +1,293 @@
+use crate::states::{calculate_completion_date, ALL_WEEKDAYS};
+use crate::Route;
+use crate::{AppState, NoSaveAppState, SyncCoordinator, SyncMode};
+use chrono::{Local, NaiveDate};
+use dioxus::prelude::*;
+use super::css_preset::*;
+
+#[component]
+pub fn TaskEdit(id: i64) -> Element {
+    let no_save_app_state = use_context::<NoSaveAppState>();
+    let sync_mode = no_save_app_state.sync_mode;
+    let sync_coordinator = use_context::<SyncCoordinator>();
+
+    let mut app_state = use_context::<AppState>();
+    let navigator = use_navigator();
+
+    let task = (app_state.tasks)().and_then(|tasks| tasks.get(&id).cloned());
+
+    // Goal Achiever tasks are the ones defined by a list of daily activities
+    let is_goal_task = task.as_ref().is_some_and(|task| task.daily_tasks.is_some());
+
+    let mut action = use_signal(|| task.as_ref().map(|t| t.action.clone()).unwrap_or_default());
+    let mut count_per_day = use_signal(|| task.as_ref().map(|t| t.count_per_day).unwrap_or(1.0_f32));
+    let mut unit = use_signal(|| task.as_ref().map(|t| t.unit.clone()).unwrap_or_default());
+    let mut start = use_signal(|| task.as_ref().map(|t| t.start.to_string()).unwrap_or_default());
+    let mut end = use_signal(|| task.as_ref().map(|t| t.end.to_string()).unwrap_or_default());
+    let mut selected_dow = use_signal(|| task.as_ref().map(|t| t.effective_dow.clone()).unwrap_or_default());
+    let mut task_name = use_signal(|| {
+        task.as_ref()
+            .and_then(|t| t.name.clone())
+            .unwrap_or_default()
+    });
+    let mut raw_acts = use_signal(|| {
+        task.as_ref()
+            .and_then(|t| t.daily_tasks.clone())
+            .map(|acts| acts.join("\n"))
+            .unwrap_or_default()
+    });
+    let mut submit_return_msg = use_signal(|| "".to_string());
+
+    let enable_submit = use_memo(move || {
+        let fields_ok = if is_goal_task {
+            !raw_acts().trim().is_empty() && !task_name().is_empty()
+        } else {
+            !action().is_empty() && count_per_day() > 0.0_f32 && !unit().is_empty()
+        };
+
+        fields_ok
+            && !selected_dow.is_empty()
+            && sync_mode() != SyncMode::Pushing
+            && sync_mode() != SyncMode::Pulling
+    });
+
+    if task.is_none() {
+        return rsx! {
+            div {
+                class: CSS_CONTENT_CARD,
+                p { "Task not found." }
+            }
+        };
+    }
+
+    rsx! {
+        div {
+            class: CSS_CONTENT_CARD,
+
+            h2 { class: "text-xl font-bold", "✏️ Edit your task" }
+            p { class: "text-sm text-gray-500", "Your progress so far is kept as it is." }
+
+            if is_goal_task {
+                div {
+                    label { class: "block text-sm font-medium text-gray-700", "Name of this challenge" }
+                    input {
+                        r#type: "text",
+                        class: "w-full border border-gray-300 rounded-md shadow-sm p-2",
+                        value: "{task_name}",
+                        oninput: move |e| task_name.set(e.value())
+                    }
+                }
+
+                div {
+                    label { class: "block text-sm font-medium text-gray-700", "Start Date" }
+                    input {
+                        r#type: "date",
+                        class: "mt-1 block w-full border border-gray-300 rounded-md shadow-sm p-2",
+                        value: "{start}",
+                        oninput: move |e| start.set(e.value().clone())
+                    }
+                }
+
+                div {
+                    label {
+                        class: "block text-sm font-medium text-gray-700",
+                        "Daily Activities (One line is one day):"
+                    }
+                    textarea {
+                        class: "mt-1 block w-full border border-gray-300 rounded-md shadow-sm p-2 h-32",
+                        value: "{raw_acts}",
+                        oninput: move |e| raw_acts.set(e.value()),
+                    }
+                }
+            } else {
+                div {
+                    class: "text-sm text-gray-800 font-medium",
+                    "Every day, I want to"
+                }
+
+                div {
+                    class: "flex gap-x-4 items-center",
+
+                    div {
+                        input {
+                            r#type: "text",
+                            class: "w-full border border-gray-300 rounded-md shadow-sm p-2",
+                            placeholder: "Action",
+                            value: "{action}",
+                            oninput: move |e| action.set(e.value())
+                        }
+                    }
+
+                    div {
+                        class: "w-1/6",
+                        input {
+                            r#type: "number",
+                            step: "any",
+                            min: "0",
+                            class: "w-full border border-gray-300 rounded-md shadow-sm p-2",
+                            placeholder: "Count",
+                            value: "{count_per_day}",
+                            oninput: move |e| {
+                                if let Ok(num) = e.value().parse::<f32>() {
+                                    count_per_day.set(num)
+                                } else {
+                                    count_per_day.set(0.0_f32)
+                                }
+                            }
+                        }
+                    }
+
+                    div {
+                        class: "w-1/3",
+                        input {
+                            r#type: "text",
+                            class: "w-full border border-gray-300 rounded-md shadow-sm p-2",
+                            placeholder: "Unit",
+                            autocomplete: "off",
+                            spellcheck: "false",
+                            value: "{unit}",
+                            oninput: move |e| unit.set(e.value().clone())
+                        }
+                    }
+                }
+
+                div {
+                    label { class: "block text-sm font-medium text-gray-700", "Custom name (optional)" }
+                    input {
+                        r#type: "text",
+                        class: "w-full border border-gray-300 rounded-md shadow-sm p-2",
+                        value: "{task_name}",
+                        oninput: move |e| task_name.set(e.value())
+                    }
+                }
+
+                div {
+                    label { class: "block text-sm font-medium text-gray-700", "Start Date" }
+                    input {
+                        r#type: "date",
+                        class: "mt-1 block w-full border border-gray-300 rounded-md shadow-sm p-2",
+                        value: "{start}",
+                        oninput: move |e| start.set(e.value().clone())
+                    }
+                }
+
+                div {
+                    label { class: "block text-sm font-medium text-gray-700", "End Date" }
+                    input {
+                        r#type: "date",
+                        class: "mt-1 block w-full border border-gray-300 rounded-md shadow-sm p-2",
+                        value: "{end}",
+                        oninput: move |e| end.set(e.value().clone())
+                    }
+                }
+            }
+
+            div {
+                label {
+                    class: "block text-sm font-medium text-gray-700",
+                    "This task will be effective on these days:",
+                },
+
+                div {
+                    class: "flex flex-wrap gap-4 mt-2",
+                    {
+                        ALL_WEEKDAYS.iter().map(|d| {
+                            let short_label = d.to_string();
+                            let selected = selected_dow.read().contains(d);
+
+                            rsx! {
+                                label {
+                                    class: "inline-flex items-center space-x-2 cursor-pointer select-none",
+                                    input {
+                                        r#type: "checkbox",
+                                        checked: selected,
+                                        onchange: move |evt| {
+                                            let is_now_checked = evt.value() == "true";
+                                            if is_now_checked {
+                                                if !selected_dow.read().contains(d) {
+                                                    selected_dow.write().push(d.to_owned());
+                                                }
+                                            } else if selected_dow.read().contains(d) {
+                                                selected_dow.write().retain(|e| e != d);
+                                            }
+                                        }
+                                    },
+                                    span { "{short_label}" }
+                                }
+                            }
+                        })
+                    }
+                }
+            }
+
+            div {
+                class: "pt-4 flex items-center gap-x-4",
+                button {
+                    disabled: "{!enable_submit()}",
+                    class: format_args!(
+                        "font-semibold py-2 px-4 rounded transition-colors duration-300 {}",
+                        if enable_submit() {
+                            "bg-blue-600 hover:bg-blue-700 text-white cursor-pointer"
+                        } else {
+                            "bg-gray-300 text-gray-500 cursor-not-allowed"
+                        }
+                    ),
+                    onclick: move |_| {
+                        let Ok(start_date) = NaiveDate::parse_from_str(&start(), "%Y-%m-%d") else {
+                            submit_return_msg.set("❌ Task update failed! Please check dates!".to_string());
+                            return;
+                        };
+
+                        selected_dow.write().sort_by_key(|d| d.num_days_from_monday());
+
+                        let (end_date, daily_tasks) = if is_goal_task {
+                            let fmt_acts = raw_acts().lines().map(|line| line.trim().to_string()).filter(|line| !line.is_empty()).collect::<Vec<String>>();
+                            (calculate_completion_date(fmt_acts.len(), start_date, selected_dow()), Some(fmt_acts))
+                        } else {
+                            (NaiveDate::parse_from_str(&end(), "%Y-%m-%d").ok(), None)
+                        };
+
+                        let Some(end_date) = end_date else {
+                            submit_return_msg.set("❌ Task update failed! Please check dates!".to_string());
+                            return;
+                        };
+
+                        let mut tasks_guard = app_state.tasks.write();
+                        if let Some(task) = tasks_guard.as_mut().and_then(|tasks| tasks.get_mut(&id)) {
+                            if !is_goal_task {
+                                task.action = action();
+                                task.count_per_day = count_per_day();
+                                task.unit = unit();
+                            }
+                            task.name = if task_name().is_empty() { None } else { Some(task_name()) };
+                            task.start = start_date;
+                            task.end = end_date;
+                            task.effective_dow = selected_dow();
+                            task.daily_tasks = daily_tasks;
+
+                            let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+                            submit_return_msg.set(format!("✅ Updated the task ({})", now));
+                            sync_coordinator.request_push("Edited a task");
+                        }
+                    },
+                    "Save",
+                },
+
+                button {
+                    class: "bg-gray-100 text-gray-800 font-medium py-2 px-4 rounded-lg transition-all hover:ring hover:ring-gray-300 hover:ring-offset-2 cursor-pointer",
+                    onclick: move |_| {
+                        navigator.push(Route::Director { pagename: format!("TaskVisual/{}", id) });
+                    },
+                    "⬅️ Back to Task",
+                },
+
+                if !submit_return_msg().is_empty() {
+                    div {
+                        class: "text-sm text-gray-700",
+                        "{submit_return_msg}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,11 +1,44 @@
-use crate::states::{AppState, SerializableState};
-use crate::Route;
+use crate::states::{task_lead, task_streaks, AppState, Lead, MyTask, SerializableState};
+use crate::{Route, SyncCoordinator};
+use chrono::Local;
 use dioxus::prelude::*;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
 use super::css_preset::*;
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use crate::states::import_data;
 
+#[derive(PartialEq, Clone, Copy, Debug, EnumIter)]
+enum TaskOrder {
+    Oldest,
+    FurthestBehind,
+}
+
+impl std::fmt::Display for TaskOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TaskOrder::Oldest => write!(f, "Oldest first"),
+            TaskOrder::FurthestBehind => write!(f, "Furthest behind first"),
+        }
+    }
+}
+
+// Summed in effective days, since the tasks do not share a unit
+fn lead_banner(leads: &[Lead]) -> String {
+    let total_days: f32 = leads.iter().map(|lead| lead.days).sum();
+    let n_behind = leads.iter().filter(|lead| lead.units < 0.0).count();
+
+    format!(
+        "{} You are {:.1} day(s) {} the Parallel Universe in total. {} task(s) ahead, {} behind.",
+        if total_days < 0.0 { "🐢" } else { "🚀" },
+        total_days.abs(),
+        if total_days < 0.0 { "behind" } else { "ahead of" },
+        leads.len() - n_behind,
+        n_behind
+    )
+}
+
 #[component]
 pub fn TaskList() -> Element {
     let app_state = use_context::<AppState>();
@@ -14,17 +47,20 @@ pub fn TaskList() -> Element {
     // These variables are created for accepting imports for web targets
     let mut app_state_import = use_context::<AppState>();
     let mut imported_serializable_state = use_signal::<Option<SerializableState>>(|| None);
+    let app_state_for_secrets = use_context::<AppState>();
 
     use_memo(move || {
-        if let Some(state) = imported_serializable_state() {
-            if let Ok(state) = AppState::try_from(state) {
-                app_state_import.tasks.set((state.tasks)());
-                app_state_import.github_pat.set((state.github_pat)());
-                app_state_import.gist_id.set((state.gist_id)());
-                app_state_import
-                    .gist_file_name
-                    .set((state.gist_file_name)());
-            }
+        if let Some(mut state) = imported_serializable_state() {
+            state.keep_secrets_from(&SerializableState::from(&app_state_for_secrets));
+            let state = AppState::from(state);
+            app_state_import.tasks.set((state.tasks)());
+            app_state_import.sync_backend.set((state.sync_backend)());
+            app_state_import.gist_config.set((state.gist_config)());
+            app_state_import.webdav_config.set((state.webdav_config)());
+            app_state_import.s3_config.set((state.s3_config)());
+            app_state_import.folder_config.set((state.folder_config)());
+            app_state_import.sync_passphrase.set((state.sync_passphrase)());
+            app_state_import.quarantine.set((state.quarantine)());
         }
     });
 
@@ -37,6 +73,30 @@ pub fn TaskList() -> Element {
     });
 
     let mut archive_view = use_signal(|| false);
+    let mut task_order = use_signal(|| TaskOrder::Oldest);
+
+    let today = Local::now().date_naive();
+    let mut listed: Vec<(MyTask, Lead)> = (app_state.tasks)()
+        .unwrap_or_default()
+        .into_values()
+        .filter(|task| if archive_view() { task.archive } else { !task.archive })
+        .map(|task| {
+            let lead = task_lead(&task, today);
+            (task, lead)
+        })
+        .collect();
+
+    match task_order() {
+        TaskOrder::Oldest => listed.sort_by_key(|(task, _)| task.id),
+        TaskOrder::FurthestBehind => listed.sort_by(|(_, a), (_, b)| a.days.total_cmp(&b.days)),
+    }
+
+    let mut quarantine = app_state.quarantine;
+    let mut confirm_discard = use_signal(|| false);
+    let sync_coordinator = use_context::<SyncCoordinator>();
+
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    let app_state_for_import_secrets = use_context::<AppState>();
 
     #[cfg(any(target_os = "android", target_os = "ios"))]
     let div_export_import_data_buttons = rsx! {};
@@ -59,13 +119,17 @@ pub fn TaskList() -> Element {
                 onclick: move |_| {
 
                     #[cfg(not(target_arch = "wasm32"))]
-                    if let Some(imported_serializable_state) = import_data() {
-                        if let Ok(imported_app_state) = TryInto::<AppState>::try_into(imported_serializable_state) {
-                            app_state_import.tasks.set((imported_app_state.tasks)());
-                            app_state_import.github_pat.set((imported_app_state.github_pat)());
-                            app_state_import.gist_id.set((imported_app_state.gist_id)());
-                            app_state_import.gist_file_name.set((imported_app_state.gist_file_name)());
-                        }
+                    if let Some(mut imported_serializable_state) = import_data() {
+                        imported_serializable_state.keep_secrets_from(&SerializableState::from(&app_state_for_import_secrets));
+                        let imported_app_state = AppState::from(imported_serializable_state);
+                        app_state_import.tasks.set((imported_app_state.tasks)());
+                        app_state_import.sync_backend.set((imported_app_state.sync_backend)());
+                        app_state_import.gist_config.set((imported_app_state.gist_config)());
+                        app_state_import.webdav_config.set((imported_app_state.webdav_config)());
+                        app_state_import.s3_config.set((imported_app_state.s3_config)());
+                        app_state_import.folder_config.set((imported_app_state.folder_config)());
+                        app_state_import.sync_passphrase.set((imported_app_state.sync_passphrase)());
+                        app_state_import.quarantine.set((imported_app_state.quarantine)());
                     }
 
                     #[cfg(target_arch = "wasm32")]
@@ -82,16 +146,42 @@ pub fn TaskList() -> Element {
         div {
             class: CSS_CONTENT_CARD,
 
+            if !quarantine().is_empty() {
+                div {
+                    class: "flex items-center justify-between gap-4 p-3 rounded-lg bg-amber-50 border border-amber-200",
+                    p {
+                        class: "text-sm text-amber-800",
+                        {
+                            format!(
+                                "⚠️ {} task(s) could not be read and were set aside. They are kept in your saved data and in exports until discarded.",
+                                quarantine().len()
+                            )
+                        }
+                    }
+                    button {
+                        class: "bg-red-100 hover:bg-red-400 text-white font-medium py-2 px-4 rounded-lg transition-all cursor-pointer whitespace-nowrap",
+                        onclick: move |_| {
+                            if confirm_discard() {
+                                quarantine.set(vec![]);
+                                confirm_discard.set(false);
+                                // Pushes were refused while they were set aside
+                                sync_coordinator.retry_pending("Discarded unreadable tasks");
+                            } else {
+                                confirm_discard.set(true);
+                            }
+                        },
+                        if confirm_discard() {"Confirm Discarding"} else {"🗑 Discard"}
+                    }
+                }
+            }
+
             if has_tasks() {
                 div {
                     div {
                         class: "flex items-center justify-between my-2",
                         p {
                             class: "font-semibold text-gray-800 text-lg",
-                            {
-                                let n_tasks = (app_state.tasks)().unwrap().values().filter(
-                                    |task| if archive_view() {task.archive} else {!task.archive}).count();
-                                format!("🗂 You have {} {} task(s):", n_tasks, if archive_view() {"archived"} else {"active"})}
+                            { format!("🗂 You have {} {} task(s):", listed.len(), if archive_view() {"archived"} else {"active"}) }
                         },
 
                         button {
@@ -103,14 +193,49 @@ pub fn TaskList() -> Element {
                         },
                     }
 
+                    if !archive_view() && !listed.is_empty() {
+                        p {
+                            class: "my-2 p-3 rounded-lg bg-blue-50 border border-blue-200 text-center font-semibold text-gray-800",
+                            { lead_banner(&listed.iter().map(|(_, lead)| *lead).collect::<Vec<_>>()) }
+                        }
+                    }
+
+                    div {
+                        class: "flex items-center justify-end gap-2 my-2",
+                        Link {
+                            class: "mr-auto text-sm text-blue-700 hover:underline",
+                            to: Route::Director { pagename: "ActivityCalendar".to_string() },
+                            "📅 Activity calendar"
+                        }
+                        label { class: "text-sm text-gray-600", "Sort:" }
+                        select {
+                            class: "border rounded p-1 text-sm",
+                            onchange: move |evt| {
+                                if let Some(order) = TaskOrder::iter().find(|order| format!("{:?}", order) == evt.value()) {
+                                    task_order.set(order);
+                                }
+                            },
+                            {
+                                TaskOrder::iter().map(|order| rsx! {
+                                    option {
+                                        value: "{order:?}",
+                                        selected: task_order() == order,
+                                        "{order}"
+                                    }
+                                })
+                            }
+                        }
+                    }
+
                     div {
                         class: "grid grid-cols-1 gap-4",
                         {
-                            (app_state.tasks)().unwrap().values().filter(
-                                |task| if archive_view() {task.archive} else {!task.archive}).map(|task| {
+                            listed.iter().map(|(task, lead)| {
                                 let id = task.id;
+                                let streaks = task_streaks(task, today);
                                 rsx! {
                                     Link {
+                                        key: "{id}",
                                         to: Route::Director { pagename: format!("TaskVisual/{}", id) },
                                         class: "relative block bg-white shadow-md rounded-xl p-4 border border-gray-100 hover:shadow-lg transition-shadow hover:ring-2 hover:ring-blue-300",
                                         h3 {
@@ -124,9 +249,19 @@ pub fn TaskList() -> Element {
                                             }
                                         }
 
+                                        p {
+                                            class: if lead.units < 0.0 { "text-sm font-semibold text-red-700" } else { "text-sm font-semibold text-green-700" },
+                                            { lead.describe(&task.unit) }
+                                        }
+
+                                        p {
+                                            class: "text-sm text-orange-700",
+                                            { streaks.describe() }
+                                        }
+
                                         p {
                                             class: "text-sm text-gray-600",
-                                            { format!("Done so far: {:.1} {}", task.count_accum, task.unit) }
+                                            { format!("Done so far: {:.1} {}", task.count_accum(), task.unit) }
                                         }
 
                                         p {
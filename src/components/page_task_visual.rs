@@ -1,58 +1,19 @@
+use crate::states::{
+    fill_ratio_parallel_universe, fill_ratio_user_universe, generate_date_range, task_forecast,
+    task_lead, task_streaks, Projection,
+};
 use crate::Route;
-use crate::{AppState, NoSaveAppState, SyncMode};
-use chrono::{Datelike, Duration, Local, NaiveDate, Timelike};
+use crate::{AppState, SyncCoordinator};
+use chrono::{Datelike, Local};
 use dioxus::prelude::*;
-use std::cmp::Ordering;
 use super::css_preset::*;
 
 const COLLAPSE_TO_TODAY_ICON: Asset = asset!("/assets/png/collapse_to_today.png");
 const COLLAPSE_TO_DONE_ICON: Asset = asset!("/assets/png/collapse_to_done.png");
 
-fn generate_date_range(
-    start: NaiveDate,
-    end: NaiveDate,
-    effective_dow: Vec<chrono::Weekday>,
-) -> Vec<NaiveDate> {
-    let mut dates = vec![];
-    let mut current = start;
-    while current <= end {
-        if effective_dow.contains(&current.weekday()) {
-            dates.push(current);
-        }
-        current += Duration::days(1);
-    }
-    dates
-}
-
-fn fill_ratio_parallel_universe(date: NaiveDate, today: NaiveDate) -> f32 {
-    match date.cmp(&today) {
-        Ordering::Less => 1.0,
-        Ordering::Equal => {
-            let now = Local::now().time();
-            now.num_seconds_from_midnight() as f32 / 86400.0
-        }
-        Ordering::Greater => 0.0,
-    }
-}
-
-fn fill_ratio_user_universe(index: usize, count_per_day: f32, count_accum: f32) -> f32 {
-    let full_boxes = (count_accum / count_per_day) as usize;
-    let remaining = count_accum % count_per_day;
-
-    match index.cmp(&full_boxes) {
-        Ordering::Less => 1.0,
-        Ordering::Equal => remaining / count_per_day,
-        Ordering::Greater => 0.0,
-    }
-}
-
 #[component]
 pub fn TaskVisual(id: i64) -> Element {
-    let no_save_app_state = use_context::<NoSaveAppState>();
-    let mut sync_msg = no_save_app_state.sync_msg;
-    let mut sync_mode = no_save_app_state.sync_mode;
-    let mut fire_push_after_deletion = no_save_app_state.fire_push_after_deletion;
-    let mut fire_push_after_vis_change = use_signal(|| false);
+    let sync_coordinator = use_context::<SyncCoordinator>();
 
     let mut app_state = use_context::<AppState>();
     let navigator = use_navigator();
@@ -69,18 +30,24 @@ pub fn TaskVisual(id: i64) -> Element {
     }
 
     let task = task.unwrap();
-    let dates = generate_date_range(task.start, task.end, task.effective_dow);
+    let dates = generate_date_range(task.start, task.end, task.effective_dow.clone());
     let today = Local::now().date_naive();
 
     let total_days = dates.len();
     let days_passed_inclusive = dates.iter().filter(|&&d| d <= today).count();
 
-    let user_accomplished = task.count_accum;
+    let user_accomplished = task.count_accum();
+    let logged_per_day = task.logged_per_day();
     let user_remaining = (total_days as f32 * task.count_per_day) - user_accomplished;
 
     let parallel_accomplished = days_passed_inclusive as f32 * task.count_per_day;
     let parallel_remaining = (total_days as f32 * task.count_per_day) - parallel_accomplished;
 
+    let lead = task_lead(&task, today);
+    let forecast = task_forecast(&task, today);
+    let streaks = task_streaks(&task, today);
+    let pace_warning_multiple = (app_state.pace_warning_multiple)();
+
     let mut show_details = use_signal(|| true);
     let has_details = use_signal(|| task.daily_tasks.is_some());
     let mut n_clicks_on_remove = use_signal(|| 0_i64);
@@ -88,33 +55,6 @@ pub fn TaskVisual(id: i64) -> Element {
     let mut collapse_to_today = use_signal(|| false);
     let mut collapse_to_done = use_signal(|| false);
 
-    use_effect(move || {
-        if fire_push_after_vis_change() {
-            spawn({
-                sync_mode.set(SyncMode::Pushing);
-                async move {
-                    let mut app_state = use_context::<AppState>();
-                    match app_state.push().await {
-                        Ok(_) => {
-                            sync_msg.set(
-                                "✅ Automatic push was successful after changing the visibility of a task!"
-                                    .to_string(),
-                            );
-                            sync_mode.set(SyncMode::InSync);
-                        }
-                        Err(e) => {
-                            sync_msg.set(format!(
-                                "⚠️ Automatic push failed after changing the visibility of a task: {}",
-                                e
-                            ));
-                            sync_mode.set(SyncMode::NotSynced);
-                        }
-                    }
-                }
-            });
-        }
-    });
-
     rsx! {
         div {
             class: CSS_CONTENT_CARD,
@@ -202,7 +142,7 @@ pub fn TaskVisual(id: i64) -> Element {
             // A single grid for each available date
             {
                 let mut show_ellipsis = true;
-                let mut n_done_days = task.count_accum / task.count_per_day;
+                let mut n_done_days = user_accomplished / task.count_per_day;
 
                 dates.iter().enumerate().map(move |(i, &date)| {
                     n_done_days -= 1.0;
@@ -227,7 +167,7 @@ pub fn TaskVisual(id: i64) -> Element {
                     }
 
                     let parallel_ratio = fill_ratio_parallel_universe(date, today);
-                    let user_ratio = fill_ratio_user_universe(i, task.count_per_day, task.count_accum);
+                    let user_ratio = fill_ratio_user_universe(i, task.count_per_day, user_accomplished);
                     rsx! {
                         div {
                             class: "grid grid-cols-3 gap-1 items-center p-2 border border-gray-200 rounded-lg mb-0.5",
@@ -252,6 +192,13 @@ pub fn TaskVisual(id: i64) -> Element {
                                     }
                                     }
 
+                                if let Some(amount) = logged_per_day.get(&date) {
+                                    p {
+                                        class: "text-center text-xs text-purple-700",
+                                        "+{amount:.1} logged on this day",
+                                    }
+                                }
+
                                 if let Some(daily_tasks) = &task.daily_tasks {
                                     if show_details() {
                                         p {
@@ -295,7 +242,82 @@ pub fn TaskVisual(id: i64) -> Element {
                     p { "Remaining: {user_remaining.max(0.0):.1}" }
                 }
             }
+
+            p {
+                class: if lead.units < 0.0 {
+                    "text-center font-semibold text-red-700"
+                } else {
+                    "text-center font-semibold text-green-700"
+                },
+                { format!("{} {}", if lead.units < 0.0 { "🐢" } else { "🚀" }, lead.describe(&task.unit)) }
+            }
+
+            p {
+                class: "text-center text-sm font-semibold text-orange-700",
+                { streaks.describe() }
+            }
+
+            div {
+                class: "text-center text-sm text-gray-700 space-y-1",
+                p {
+                    {
+                        match forecast.projected_finish {
+                            Projection::Done => "🏆 All done!".to_string(),
+                            Projection::On(date) => format!(
+                                "📈 At your pace of {:.1} {} per effective day, you will finish on {}",
+                                forecast.pace, task.unit, date
+                            ),
+                            Projection::Never => "📈 Log some progress to see when you will finish".to_string(),
+                        }
+                    }
+                }
+
+                if forecast.projected_finish != Projection::Done {
+                    p {
+                        {
+                            match forecast.required_pace {
+                                Some(required_pace) => format!(
+                                    "🎯 To finish by {} you now need {:.1} {} per effective day ({} day(s) left)",
+                                    task.end, required_pace, task.unit, forecast.days_left
+                                ),
+                                None => format!("🎯 The deadline {} has passed", task.end),
+                            }
+                        }
+                    }
+                }
+
+                if forecast.required_pace.is_some() && forecast.is_slipping(task.count_per_day, pace_warning_multiple) {
+                    p {
+                        class: "font-semibold text-amber-700",
+                        {
+                            format!(
+                                "⚠️ That is more than {}× the planned {} {} per day. This goal is slipping.",
+                                pace_warning_multiple, task.count_per_day, task.unit
+                            )
+                        }
+                    }
+                }
+            }
             
+            div {
+                class: "flex justify-center gap-4",
+                button {
+                    class: "bg-gray-100 text-gray-800 font-medium py-2 px-4 rounded-lg transition-all hover:ring hover:ring-gray-300 hover:ring-offset-2 cursor-pointer",
+                    onclick: move |_| {
+                        navigator.push(Route::Director { pagename: format!("ActionHistory/{}", id) });
+                    },
+                    "🕘 History",
+                }
+
+                button {
+                    class: "bg-gray-100 text-gray-800 font-medium py-2 px-4 rounded-lg transition-all hover:ring hover:ring-gray-300 hover:ring-offset-2 cursor-pointer",
+                    onclick: move |_| {
+                        navigator.push(Route::Director { pagename: format!("TaskEdit/{}", id) });
+                    },
+                    "✏️ Edit",
+                }
+            }
+
             div {
                 class: {format!("p-6 max-w-5xl grid mx-auto {}", if task.archive {"grid-cols-3"} else {"grid-cols-2"})},
     
@@ -328,7 +350,7 @@ pub fn TaskVisual(id: i64) -> Element {
                                     }
                                 }
     
-                                fire_push_after_vis_change.set(true);
+                                sync_coordinator.request_push("Changed the visibility of a task");
                                 n_clicks_on_archive.set(0);
                             }
                         },
@@ -358,7 +380,7 @@ pub fn TaskVisual(id: i64) -> Element {
                                         tasks.remove(&id);
                                     }
     
-                                    fire_push_after_deletion.set(true);
+                                    sync_coordinator.request_push("Deleted a task");
                                     navigator.push(Route::TaskList);
                                 }
                             },
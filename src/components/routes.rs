@@ -1,8 +1,13 @@
 use super::header_navbar::HeaderNavbar;
 use super::page_about::About;
+use super::page_action_history::ActionHistory;
 use super::page_action_log::ActionLog;
+use super::page_activity_calendar::ActivityCalendar;
+use super::page_backup_restore::BackupRestore;
+use super::page_gist_history::GistHistory;
 use super::page_setting::Setting;
 use super::page_task_create::TaskCreate;
+use super::page_task_edit::TaskEdit;
 use super::page_task_list::TaskList;
 use super::page_task_visual::TaskVisual;
 use dioxus::prelude::*;
@@ -28,10 +33,27 @@ pub fn Director(pagename: String) -> Element {
             };
             rsx!(TaskVisual { id: id })
         }
+        "ActionHistory" => {
+            let id = match parts.get(1).and_then(|s| s.parse::<i64>().ok()) {
+                Some(valid_id) => valid_id,
+                None => return rsx!(TaskList {}),
+            };
+            rsx!(ActionHistory { id: id })
+        }
+        "TaskEdit" => {
+            let id = match parts.get(1).and_then(|s| s.parse::<i64>().ok()) {
+                Some(valid_id) => valid_id,
+                None => return rsx!(TaskList {}),
+            };
+            rsx!(TaskEdit { id: id })
+        }
         "TaskCreate" => rsx!(TaskCreate {}),
         "ActionLog" => rsx!(ActionLog {}),
+        "ActivityCalendar" => rsx!(ActivityCalendar {}),
         "About" => rsx!(About {}),
         "Setting" => rsx!(Setting {}),
+        "BackupRestore" => rsx!(BackupRestore {}),
+        "GistHistory" => rsx!(GistHistory {}),
         "TaskList" => rsx!(TaskList {}),
         _ => rsx!(TaskList {}),
     }
@@ -0,0 +1,79 @@
+use crate::AppState;
+use chrono::Utc;
+use dioxus::prelude::*;
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+use crate::states::pick_folder;
+
+#[component]
+pub fn FolderSetting() -> Element {
+    let app_state = use_context::<AppState>();
+    let mut folder_config = app_state.folder_config;
+    // What was last synced belongs to the previous target, so the next sync merges in full
+    let mut sync_base = app_state.sync_base;
+
+    // Each device writes its own file, so it needs a name the other devices do not use
+    use_effect(move || {
+        if folder_config.read().device_name.is_none() {
+            folder_config.write().device_name =
+                Some(format!("device-{}", Utc::now().timestamp_millis()));
+        }
+    });
+
+    rsx! {
+        div {
+            label { "Folder" }
+            div {
+                class: "flex gap-x-2",
+                input {
+                    class: "w-full border rounded p-2",
+                    placeholder: "A folder kept in sync by Syncthing, Dropbox, ...",
+                    value: folder_config().folder.unwrap_or_default(),
+                    oninput: move |evt| {
+                        let user_input = evt.value();
+                        if user_input.is_empty() {
+                            folder_config.write().folder = None;
+                        } else {
+                            folder_config.write().folder = Some(user_input);
+                        }
+                        sync_base.set(None);
+                    },
+                }
+                button {
+                    class: "bg-gray-100 text-gray-800 font-medium py-2 px-4 rounded-lg transition-all hover:ring hover:ring-gray-300 hover:ring-offset-2 cursor-pointer whitespace-nowrap",
+                    onclick: move |_| {
+                        #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+                        if let Some(folder) = pick_folder() {
+                            folder_config.write().folder = Some(folder);
+                            sync_base.set(None);
+                        }
+                    },
+                    "📁 Choose"
+                }
+            }
+        }
+
+        div {
+            label { "Device Name" }
+            input {
+                class: "w-full border rounded p-2",
+                autocomplete: "off",
+                spellcheck: "false",
+                value: folder_config().device_name.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        folder_config.write().device_name = None;
+                    } else {
+                        folder_config.write().device_name = Some(user_input);
+                    }
+                    sync_base.set(None);
+                },
+            }
+            p {
+                class: "text-sm text-gray-500",
+                "This device writes to \"{folder_config().device_name.unwrap_or_default()}.json\" and reads the files of your other devices."
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use crate::states::GistCandidate;
+use crate::{AppState, Route, SyncCoordinator};
+use dioxus::prelude::*;
+
+#[component]
+pub fn GistSetting() -> Element {
+    let app_state = use_context::<AppState>();
+    let mut gist_config = app_state.gist_config;
+    // What was last synced belongs to the previous target, so the next sync merges in full
+    let mut sync_base = app_state.sync_base;
+
+    let gist_id_ready = use_memo(move || {
+        gist_config()
+            .gist_id
+            .is_some_and(|gist_id| !gist_id.is_empty())
+    });
+
+    let pat_ready = use_memo(move || {
+        gist_config()
+            .github_pat
+            .is_some_and(|github_pat| !github_pat.is_empty())
+    });
+
+    let sync_coordinator = use_context::<SyncCoordinator>();
+    let navigator = use_navigator();
+    let mut setup_msg = use_signal(String::new);
+    let mut candidates = use_signal::<Option<Vec<GistCandidate>>>(|| None);
+
+    rsx! {
+        div {
+            label { "GitHub Gist ID" }
+            input {
+                class: "w-full border rounded p-2",
+                value: gist_config().gist_id.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        gist_config.write().gist_id = None;
+                    } else {
+                        gist_config.write().gist_id = Some(user_input);
+                    }
+                    sync_base.set(None);
+                },
+            }
+        }
+
+        div {
+            label { "Gist File Name" }
+            input {
+                class: "w-full border rounded p-2",
+                value: gist_config().gist_file_name.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        gist_config.write().gist_file_name = None;
+                    } else {
+                        gist_config.write().gist_file_name = Some(user_input);
+                    }
+                    sync_base.set(None);
+                },
+            }
+        }
+
+        div {
+            label { "GitHub Personal Access Token (Classic)" }
+            input {
+                r#type: "password",
+                class: "w-full border rounded p-2",
+                value: gist_config().github_pat.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        gist_config.write().github_pat = None
+                    } else {
+                        gist_config.write().github_pat = Some(user_input)
+                    }
+                },
+            }
+        }
+
+        if pat_ready() {
+            div {
+                class: "flex gap-4 justify-center",
+                button {
+                    class: "font-semibold py-2 px-4 rounded transition-colors duration-300 bg-green-600 hover:bg-green-700 text-white cursor-pointer",
+                    onclick: move |_| {
+                        setup_msg.set("Creating a secret gist ...".to_string());
+                        spawn(async move {
+                            let mut app_state = consume_context::<AppState>();
+                            match app_state.create_sync_gist().await {
+                                Ok(()) => setup_msg.set("✅ Created a secret gist and synced with it".to_string()),
+                                Err(e) => setup_msg.set(e),
+                            }
+                        });
+                    },
+                    "Create sync gist"
+                }
+
+                button {
+                    class: "font-semibold py-2 px-4 rounded transition-colors duration-300 bg-cyan-600 hover:bg-cyan-700 text-white cursor-pointer",
+                    onclick: move |_| {
+                        setup_msg.set("Looking for gists with Stay Ahead data ...".to_string());
+                        spawn(async move {
+                            match gist_config().find_gists().await {
+                                Ok(found) => {
+                                    setup_msg.set(format!("Found {} gist file(s) with Stay Ahead data", found.len()));
+                                    candidates.set(Some(found));
+                                }
+                                Err(e) => setup_msg.set(format!("❌ {}", e)),
+                            }
+                        });
+                    },
+                    "Find existing"
+                }
+            }
+
+            if !setup_msg().is_empty() {
+                p { class: "text-sm text-gray-600 text-center", "{setup_msg}" }
+            }
+
+            if let Some(found) = candidates() {
+                div {
+                    class: "space-y-2",
+                    {
+                        found.into_iter().map(|candidate| {
+                            let label = if candidate.description.is_empty() {
+                                candidate.file_name.clone()
+                            } else {
+                                format!("{} ({})", candidate.description, candidate.file_name)
+                            };
+
+                            rsx! {
+                                div {
+                                    key: "{candidate.gist_id}/{candidate.file_name}",
+                                    class: "flex items-center justify-between gap-4 bg-white border border-gray-200 rounded-lg p-2",
+                                    div {
+                                        p { class: "font-semibold", "{label}" }
+                                        p { class: "text-sm text-gray-500", "Updated {candidate.updated_at}" }
+                                    }
+                                    button {
+                                        class: "font-semibold py-1 px-3 rounded bg-blue-600 hover:bg-blue-700 text-white cursor-pointer",
+                                        onclick: move |_| {
+                                            let mut app_state = consume_context::<AppState>();
+                                            app_state.use_gist(&candidate);
+                                            candidates.set(None);
+                                            setup_msg.set(String::new());
+                                            sync_coordinator.request_pull("Switched to an existing gist");
+                                        },
+                                        "Use"
+                                    }
+                                }
+                            }
+                        })
+                    }
+                }
+            }
+        }
+
+        if gist_id_ready() && pat_ready() {
+            div {
+                class: "flex justify-center",
+                button {
+                    class: "bg-gray-100 text-gray-800 font-medium py-2 px-4 rounded-lg transition-all hover:ring hover:ring-gray-300 hover:ring-offset-2 cursor-pointer",
+                    onclick: move |_| {
+                        navigator.push(Route::Director { pagename: "GistHistory".to_string() });
+                    },
+                    "🕰 Browse gist history"
+                }
+            }
+        }
+    }
+}
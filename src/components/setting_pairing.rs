@@ -0,0 +1,123 @@
+use crate::states::{decode_qr_image, generate_qr_data_url, Pairing};
+use crate::{AppState, SerializableState, SyncCoordinator};
+use dioxus::prelude::*;
+
+#[component]
+pub fn PairingSetting() -> Element {
+    let sync_coordinator = use_context::<SyncCoordinator>();
+
+    let mut pairing_code = use_signal(String::new);
+    let mut pairing_qr = use_signal::<Option<String>>(|| None);
+    let mut pairing_msg = use_signal(String::new);
+
+    rsx! {
+        div {
+            class: "space-y-2",
+            h2 { class: "text-xl font-bold", "📱 Device Pairing" }
+            p {
+                class: "text-sm text-gray-500",
+                "Show the pairing QR code here and scan it on the other device to copy all sync settings at once. Type the same pairing code on both devices, it protects the secrets in the QR code."
+            }
+
+            div {
+                label { "Pairing code (at least 6 characters)" }
+                input {
+                    r#type: "password",
+                    class: "w-full border rounded p-2",
+                    autocomplete: "off",
+                    value: pairing_code(),
+                    oninput: move |evt| pairing_code.set(evt.value()),
+                }
+            }
+
+            div {
+                class: "flex gap-4 justify-center items-center",
+                button {
+                    class: "font-semibold py-2 px-4 rounded transition-colors duration-300 bg-blue-600 hover:bg-blue-700 text-white cursor-pointer",
+                    onclick: move |_| {
+                        let app_state = consume_context::<AppState>();
+                        let pairing = Pairing::from(&SerializableState::from(&app_state));
+
+                        match pairing.seal(&pairing_code()) {
+                            Ok(payload) => match generate_qr_data_url(payload) {
+                                Some(url) => {
+                                    pairing_msg.set(String::new());
+                                    pairing_qr.set(Some(url));
+                                }
+                                None => pairing_msg.set("❌ Not able to generate a QR code ...".to_string()),
+                            },
+                            Err(e) => pairing_msg.set(e),
+                        }
+                    },
+                    "Show pairing QR code"
+                }
+
+                label {
+                    class: "font-semibold py-2 px-4 rounded transition-colors duration-300 bg-cyan-600 hover:bg-cyan-700 text-white cursor-pointer",
+                    "Scan pairing QR code"
+                    // Opens the camera on phones, and a file picker for a screenshot elsewhere
+                    input {
+                        r#type: "file",
+                        accept: "image/*",
+                        capture: "environment",
+                        class: "hidden",
+                        onchange: move |evt| async move {
+                            let Some(file_engine) = evt.files() else {
+                                return;
+                            };
+                            let Some(file_name) = file_engine.files().into_iter().next() else {
+                                return;
+                            };
+                            let Some(bytes) = file_engine.read_file(&file_name).await else {
+                                pairing_msg.set("❌ Failed to read the image".to_string());
+                                return;
+                            };
+
+                            let paired = decode_qr_image(&bytes)
+                                .and_then(|payload| Pairing::open(&payload, &pairing_code()))
+                                .and_then(|pairing| {
+                                    let mut app_state = consume_context::<AppState>();
+                                    app_state.apply_pairing(pairing)
+                                });
+
+                            match paired {
+                                Ok(()) => {
+                                    pairing_msg.set("✅ Paired, pulling the synced data ...".to_string());
+                                    sync_coordinator.request_pull("Paired with another device");
+                                }
+                                Err(e) => pairing_msg.set(e),
+                            }
+                        },
+                    }
+                }
+            }
+
+            if !pairing_msg().is_empty() {
+                p { class: "text-sm text-gray-600 text-center", "{pairing_msg}" }
+            }
+
+            if let Some(url) = pairing_qr() {
+                div {
+                    class: "fixed inset-0 flex items-center justify-center bg-white/90 z-50",
+                    onclick: move |_| pairing_qr.set(None),
+
+                    div {
+                        class: "bg-white p-6 rounded shadow-lg z-60",
+                        onclick: |_| {},
+
+                        h3 {
+                            class: "text-lg font-semibold mb-2",
+                            "Scan on the other device:"
+                        }
+
+                        img {
+                            src: url,
+                            alt: "Pairing QR code",
+                            class: "w-64 h-64 border mx-auto"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
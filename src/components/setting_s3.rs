@@ -0,0 +1,116 @@
+use crate::AppState;
+use dioxus::prelude::*;
+
+#[component]
+pub fn S3Setting() -> Element {
+    let app_state = use_context::<AppState>();
+    let mut s3_config = app_state.s3_config;
+    // What was last synced belongs to the previous target, so the next sync merges in full
+    let mut sync_base = app_state.sync_base;
+
+    rsx! {
+        div {
+            label { "Endpoint URL" }
+            input {
+                class: "w-full border rounded p-2",
+                placeholder: "https://s3.eu-central-1.amazonaws.com",
+                value: s3_config().endpoint.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        s3_config.write().endpoint = None;
+                    } else {
+                        s3_config.write().endpoint = Some(user_input);
+                    }
+                    sync_base.set(None);
+                },
+            }
+        }
+
+        div {
+            label { "Region (optional for MinIO)" }
+            input {
+                class: "w-full border rounded p-2",
+                placeholder: "us-east-1",
+                value: s3_config().region.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        s3_config.write().region = None;
+                    } else {
+                        s3_config.write().region = Some(user_input);
+                    }
+                },
+            }
+        }
+
+        div {
+            label { "Bucket" }
+            input {
+                class: "w-full border rounded p-2",
+                value: s3_config().bucket.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        s3_config.write().bucket = None;
+                    } else {
+                        s3_config.write().bucket = Some(user_input);
+                    }
+                    sync_base.set(None);
+                },
+            }
+        }
+
+        div {
+            label { "Object Key" }
+            input {
+                class: "w-full border rounded p-2",
+                placeholder: "stay-ahead/state.json",
+                value: s3_config().object_key.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        s3_config.write().object_key = None;
+                    } else {
+                        s3_config.write().object_key = Some(user_input);
+                    }
+                    sync_base.set(None);
+                },
+            }
+        }
+
+        div {
+            label { "Access Key ID" }
+            input {
+                class: "w-full border rounded p-2",
+                autocomplete: "off",
+                value: s3_config().access_key_id.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        s3_config.write().access_key_id = None;
+                    } else {
+                        s3_config.write().access_key_id = Some(user_input);
+                    }
+                },
+            }
+        }
+
+        div {
+            label { "Secret Access Key" }
+            input {
+                r#type: "password",
+                class: "w-full border rounded p-2",
+                value: s3_config().secret_access_key.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        s3_config.write().secret_access_key = None;
+                    } else {
+                        s3_config.write().secret_access_key = Some(user_input);
+                    }
+                },
+            }
+        }
+    }
+}
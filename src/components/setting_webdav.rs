@@ -0,0 +1,82 @@
+use crate::AppState;
+use dioxus::prelude::*;
+
+#[component]
+pub fn WebDavSetting() -> Element {
+    let app_state = use_context::<AppState>();
+    let mut webdav_config = app_state.webdav_config;
+    // What was last synced belongs to the previous target, so the next sync merges in full
+    let mut sync_base = app_state.sync_base;
+
+    rsx! {
+        div {
+            label { "WebDAV URL" }
+            input {
+                class: "w-full border rounded p-2",
+                placeholder: "https://cloud.example.com/remote.php/dav/files/me",
+                value: webdav_config().url.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        webdav_config.write().url = None;
+                    } else {
+                        webdav_config.write().url = Some(user_input);
+                    }
+                    sync_base.set(None);
+                },
+            }
+        }
+
+        div {
+            label { "File Path" }
+            input {
+                class: "w-full border rounded p-2",
+                placeholder: "StayAhead/state.json",
+                value: webdav_config().file_path.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        webdav_config.write().file_path = None;
+                    } else {
+                        webdav_config.write().file_path = Some(user_input);
+                    }
+                    sync_base.set(None);
+                },
+            }
+        }
+
+        div {
+            label { "Username" }
+            input {
+                class: "w-full border rounded p-2",
+                autocomplete: "off",
+                value: webdav_config().username.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        webdav_config.write().username = None;
+                    } else {
+                        webdav_config.write().username = Some(user_input);
+                    }
+                },
+            }
+        }
+
+        div {
+            label { "Password (an app password is recommended)" }
+            input {
+                r#type: "password",
+                class: "w-full border rounded p-2",
+                value: webdav_config().password.unwrap_or_default(),
+                oninput: move |evt| {
+                    let user_input = evt.value();
+                    if user_input.is_empty() {
+                        webdav_config.write().password = None;
+                    } else {
+                        webdav_config.write().password = Some(user_input);
+                    }
+                },
+            }
+        }
+    }
+}
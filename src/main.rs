@@ -3,13 +3,20 @@ mod states;
 
 use core::sync;
 
-use components::Route;
+use components::{Recovery, Route};
 use dioxus::prelude::*;
-use states::{AppState, NoSaveAppState, SerializableState, SyncMode};
+use states::{
+    load_state, sleep, AppState, LoadError, NoSaveAppState, SerializableState, SyncCoordinator,
+    SyncMode,
+};
+use std::time::Duration;
 
 const FAVICON: Asset = asset!("/assets/icons/favicon.ico");
 const TAILWIND_CSS: Asset = asset!("/assets/css/tailwind_output.css");
 
+// Bursts of changes (e.g. typing in the settings) end up as a single save
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(800);
+
 fn main() {
     console_log::init_with_level(log::Level::Info).expect("error initializing log");
     dioxus::launch(App);
@@ -27,63 +34,144 @@ fn App() -> Element {
     });
     
     let no_save_app_state = use_context_provider(|| NoSaveAppState {
-        sync_msg: Signal::new("".to_string()),
         sync_mode: Signal::new(SyncMode::NotSynced),
-        fire_push_after_deletion: Signal::new(false),
+        sync_conflicts: Signal::new(vec![]),
+        sync_log: Signal::new(vec![]),
+        load_error: Signal::new(None),
     });
 
-    let mut sync_msg = no_save_app_state.sync_msg;
-    let mut sync_mode = no_save_app_state.sync_mode;
+    let mut load_error = no_save_app_state.load_error;
 
-    let mut app_state = use_context_provider(|| {
-        let mut app_state = match SerializableState::load() {
-            Ok(state) => match TryInto::<AppState>::try_into(state) {
-                Ok(state) => state,
-                _ => AppState {
-                    tasks: Signal::new(None),
-                    github_pat: Signal::new(None),
-                    gist_id: Signal::new(None),
-                    gist_file_name: Signal::new(None),
-                },
-            },
-            Err(_) => AppState {
-                tasks: Signal::new(None),
-                github_pat: Signal::new(None),
-                gist_id: Signal::new(None),
-                gist_file_name: Signal::new(None),
-            },
-        };
+    let mut saved_hash = use_signal::<Option<u64>>(|| None);
+    let mut save_generation = use_signal(|| 0_u64);
 
-        // Trigger a initial pull
-        let mut app_state_for_pull = app_state.clone();
-
-        spawn({
-            sync_mode.set(SyncMode::Pulling);
-
-            async move {
-                match app_state_for_pull.pull().await {
-                    Ok(_) => {
-                        sync_msg.set("✅ Initial pull was successful!".to_string());
-                        sync_mode.set(SyncMode::InSync);
-                    }
-                    Err(e) => {
-                        sync_msg.set("⚠️ Initial pull failed. Not synced!".to_string());
-                        sync_mode.set(SyncMode::NotSynced);
-                    }
-                }
+    let mut app_state = use_context_provider(|| {
+        let app_state = match load_state() {
+            Ok(state) => {
+                let app_state = AppState::from(state);
+                saved_hash.set(Some(app_state.content_hash()));
+                app_state
             }
-        });
+            Err(LoadError::FileNotFound(_)) => AppState::from(SerializableState::default()),
+            Err(e) => {
+                // Saving now would overwrite what could still be recovered
+                load_error.set(Some(e.to_string()));
+                return AppState::from(SerializableState::default());
+            }
+        };
 
         app_state
     });
 
+    let sync_coordinator = use_context_provider({
+        let app_state = app_state.clone();
+        move || SyncCoordinator::new(app_state, &no_save_app_state)
+    });
+
+    // Trigger a initial pull, then push what could not be pushed last time
+    use_hook(move || {
+        if load_error.peek().is_none() {
+            sync_coordinator.request_pull("App start");
+            sync_coordinator.retry_pending("App start");
+        }
+    });
+
+    #[cfg(target_arch = "wasm32")]
+    use_hook(move || {
+        states::on_online(move || sync_coordinator.retry_pending("Back online"));
+    });
+
+    let app_state_for_save = app_state.clone();
+
     use_effect(move || {
-        app_state.save();
+        if load_error().is_some() {
+            return;
+        }
+
+        let hash = app_state_for_save.content_hash();
+        if *saved_hash.peek() == Some(hash) {
+            return;
+        }
+
+        let generation = *save_generation.peek() + 1;
+        save_generation.set(generation);
+
+        let app_state = app_state_for_save.clone();
+        spawn(async move {
+            sleep(SAVE_DEBOUNCE).await;
+
+            // A newer change is already waiting for its own save
+            if *save_generation.peek() == generation {
+                saved_hash.set(Some(app_state.save()));
+            }
+        });
     });
 
+    // Pending changes are written right away when the app is closed.
+    // There is no event for the network coming back, so pending changes are also pushed
+    // when the window is focused instead of waiting out the backoff.
+    #[cfg(feature = "desktop")]
+    {
+        use dioxus::desktop::tao::event::Event;
+        use dioxus::desktop::WindowEvent;
+
+        let app_state = app_state.clone();
+        dioxus::desktop::use_wry_event_handler(move |event, _| match event {
+            Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                ..
+            } => flush_save(&app_state, saved_hash, load_error),
+            Event::WindowEvent {
+                event: WindowEvent::Focused(true),
+                ..
+            } => sync_coordinator.retry_pending("Window focused"),
+            _ => {}
+        });
+    }
+
+    // Android and iOS may end the app without notice once it is in the background.
+    // Pending changes are pushed when it comes back, as the network may be back too.
+    #[cfg(feature = "mobile")]
+    {
+        use dioxus::mobile::tao::event::Event;
+
+        let app_state = app_state.clone();
+        dioxus::mobile::use_wry_event_handler(move |event, _| match event {
+            Event::Suspended => flush_save(&app_state, saved_hash, load_error),
+            Event::Resumed => sync_coordinator.retry_pending("App resumed"),
+            _ => {}
+        });
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let app_state = app_state.clone();
+        use_hook(move || {
+            states::on_page_hide(move || flush_save(&app_state, saved_hash, load_error));
+        });
+    }
+
     rsx! {
         document::Link { rel: "icon", href: FAVICON }
         document::Link { rel: "stylesheet", href: TAILWIND_CSS }
-        Router::<Route> {}
+        if load_error().is_some() {
+            Recovery {}
+        } else {
+            Router::<Route> {}
+        }
+    }
+}
+
+fn flush_save(
+    app_state: &AppState,
+    mut saved_hash: Signal<Option<u64>>,
+    load_error: Signal<Option<String>>,
+) {
+    if load_error.peek().is_some() {
+        return;
+    }
+
+    if *saved_hash.peek() != Some(app_state.content_hash()) {
+        saved_hash.set(Some(app_state.save()));
     }
 }
@@ -1,20 +1,33 @@
-mod encoding;
 mod motivation;
 mod platform;
 mod qr;
-mod serializable_state;
-mod serializable_task;
 mod state;
+mod sync_coordinator;
 mod sync_mode;
-mod task;
 
-pub use encoding::{decode, encode};
 pub use motivation::MOTIVATIONAL_MSGS;
-pub use qr::generate_qr_data_url;
-pub use serializable_state::SerializableState;
+pub use platform::{
+    backup_now, list_backups, load_backup, load_state, set_aside_damaged, sleep,
+};
+pub use qr::{decode_qr_image, generate_qr_data_url};
+pub use stay_ahead_core::activity::{activity_by_day, entries_on};
+pub use stay_ahead_core::schedule::{
+    calculate_completion_date, fill_ratio_parallel_universe, fill_ratio_user_universe,
+    generate_date_range, task_forecast, task_lead, Lead, Projection, ALL_WEEKDAYS,
+};
+pub use stay_ahead_core::streak::task_streaks;
+pub use stay_ahead_core::sync_backend::{GistCandidate, GistRevision, SyncBackendKind};
+pub use stay_ahead_core::{LoadError, LogEntry, MyTask, Pairing, SerializableState, SyncConflict};
 pub use state::{AppState, NoSaveAppState};
+pub use sync_coordinator::SyncCoordinator;
 pub use sync_mode::SyncMode;
-pub use task::MyTask;
+
+/*
+ * Web
+ */
+
+#[cfg(target_arch = "wasm32")]
+pub use platform::{on_online, on_page_hide};
 
 /*
  * Desktop and Web
@@ -22,3 +35,10 @@ pub use task::MyTask;
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub use platform::import_data;
+
+/*
+ * Desktop
+ */
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+pub use platform::pick_folder;
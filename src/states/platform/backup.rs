@@ -0,0 +1,208 @@
+use super::persistence::decrypt_secrets;
+use super::STORAGE_KEY;
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::state_file_path;
+
+use crate::states::LoadError;
+use crate::SerializableState;
+use chrono::{Local, NaiveDateTime};
+use stay_ahead_core::backup::{backup_due, backups_to_prune, BACKUP_ID_FORMAT};
+
+pub const DEFAULT_BACKUP_COUNT: usize = 5;
+
+// LocalStorage holds only a few MB for the state and its backups together
+#[cfg(target_arch = "wasm32")]
+const WEB_MAX_BACKUP_COUNT: usize = 2;
+
+/*
+ * Struct Definition
+ */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupInfo {
+    pub id: String,
+    pub created: NaiveDateTime,
+    pub task_count: Option<usize>,
+}
+
+/*
+ * Rolling backups of the saved state
+ *
+ * A backup is a copy of what was saved before, taken right before it gets
+ * overwritten. Only the newest `keep` backups are kept, and room is made
+ * before a new one is written so that there are never more.
+ */
+
+pub fn rotate_backups(keep: usize) {
+    #[cfg(target_arch = "wasm32")]
+    let keep = keep.min(WEB_MAX_BACKUP_COUNT);
+
+    let due = keep > 0 && backup_due(&backup_ids(), Local::now().naive_local());
+
+    prune_backups(if due { keep - 1 } else { keep });
+
+    if due {
+        backup_now();
+    }
+}
+
+// Backups are given up before the saved state itself when storage runs out
+#[cfg(target_arch = "wasm32")]
+pub fn drop_backups() {
+    prune_backups(0);
+}
+
+pub fn backup_now() {
+    if let Some(current) = read_current() {
+        let id = Local::now().format(BACKUP_ID_FORMAT).to_string();
+        if let Err(e) = write_backup(&id, &current) {
+            log::error!("Failed to back up the saved data: {}", e);
+        }
+    }
+}
+
+fn prune_backups(keep: usize) {
+    for id in backups_to_prune(&backup_ids(), keep) {
+        delete_backup(id);
+    }
+}
+
+// Newest first
+pub fn list_backups() -> Vec<BackupInfo> {
+    backup_ids()
+        .into_iter()
+        .rev()
+        .filter_map(|id| {
+            let created = NaiveDateTime::parse_from_str(&id, BACKUP_ID_FORMAT).ok()?;
+            let task_count = read_backup(&id)
+                .and_then(|data| SerializableState::from_json(&data).ok())
+                .map(|state| state.tasks.map(|tasks| tasks.len()).unwrap_or(0));
+
+            Some(BackupInfo {
+                id,
+                created,
+                task_count,
+            })
+        })
+        .collect()
+}
+
+pub fn load_backup(id: &str) -> Result<SerializableState, LoadError> {
+    let data = read_backup(id).ok_or_else(|| {
+        LoadError::FileNotFound(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("Backup {} does not exist", id),
+        ))
+    })?;
+
+    let mut parsed = SerializableState::from_json(&data)?;
+
+    decrypt_secrets(&mut parsed);
+
+    Ok(parsed)
+}
+
+/*
+ * Backup storage
+ *
+ * Files next to the state file for IOS, Android, and desktop targets
+ * LocalStorage entries for web target
+ */
+
+#[cfg(not(target_arch = "wasm32"))]
+fn backup_path(id: &str) -> std::path::PathBuf {
+    state_file_path().with_file_name(format!("{}.backup-{}.json", STORAGE_KEY, id))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn backup_ids() -> Vec<String> {
+    let prefix = format!("{}.backup-", STORAGE_KEY);
+    let state_file = state_file_path();
+    let Some(Ok(entries)) = state_file.parent().map(std::fs::read_dir) else {
+        return vec![];
+    };
+
+    let mut ids: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            Some(file_name.strip_prefix(&prefix)?.strip_suffix(".json")?.to_string())
+        })
+        .collect();
+
+    ids.sort();
+    ids
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_current() -> Option<String> {
+    std::fs::read_to_string(state_file_path()).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_backup(id: &str) -> Option<String> {
+    std::fs::read_to_string(backup_path(id)).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_backup(id: &str, data: &str) -> Result<(), String> {
+    std::fs::write(backup_path(id), data).map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn delete_backup(id: &str) {
+    let _ = std::fs::remove_file(backup_path(id));
+}
+
+#[cfg(target_arch = "wasm32")]
+fn backup_key(id: &str) -> String {
+    format!("{}_Backup_{}", STORAGE_KEY, id)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn backup_ids() -> Vec<String> {
+    use gloo_storage::{LocalStorage, Storage};
+
+    let storage = LocalStorage::raw();
+    let prefix = backup_key("");
+    let length = storage.length().unwrap_or(0);
+
+    let mut ids: Vec<String> = (0..length)
+        .filter_map(|index| storage.key(index).ok().flatten())
+        .filter_map(|key| Some(key.strip_prefix(&prefix)?.to_string()))
+        .collect();
+
+    ids.sort();
+    ids
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_current() -> Option<String> {
+    use gloo_storage::{LocalStorage, Storage};
+
+    LocalStorage::raw().get_item(STORAGE_KEY).ok().flatten()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_backup(id: &str) -> Option<String> {
+    use gloo_storage::{LocalStorage, Storage};
+
+    LocalStorage::raw().get_item(&backup_key(id)).ok().flatten()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_backup(id: &str, data: &str) -> Result<(), String> {
+    use gloo_storage::{LocalStorage, Storage};
+
+    LocalStorage::raw()
+        .set_item(&backup_key(id), data)
+        .map_err(|_| "LocalStorage is full or unavailable".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn delete_backup(id: &str) {
+    use gloo_storage::{LocalStorage, Storage};
+
+    LocalStorage::delete(backup_key(id));
+}
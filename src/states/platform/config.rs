@@ -1 +1 @@
-pub const STORAGE_KEY: &str = "PaleggWorks_StayAhead_AppState";
+pub use stay_ahead_core::state_file::STORAGE_KEY;
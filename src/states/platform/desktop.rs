@@ -1,12 +1,11 @@
-use super::STORAGE_KEY;
-use crate::states::{decode, encode};
+use super::persistence::decrypt_secrets;
 use crate::{AppState, SerializableState};
+use stay_ahead_core::state_file::state_file_in;
 use std::path::PathBuf;
 
+// The command line finds the same file, see `stay_ahead_cli`
 pub fn state_file_path() -> PathBuf {
-    dirs::data_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(STORAGE_KEY.to_string() + ".json")
+    state_file_in(&dirs::data_dir().unwrap_or_else(|| PathBuf::from(".")))
 }
 
 /*
@@ -19,10 +18,8 @@ impl AppState {
 
         let mut serializable: SerializableState = self.into();
 
-        if let Some(github_pat) = serializable.github_pat {
-            let encrypted_pat = encode(&github_pat);
-            serializable.github_pat = Some(encrypted_pat);
-        }
+        // Secrets stay on this device
+        serializable.strip_secrets();
 
         if let Ok(json) = serde_json::to_string_pretty(&serializable) {
             if let Some(path) = rfd::FileDialog::new()
@@ -49,12 +46,21 @@ pub fn import_data() -> Option<SerializableState> {
         .pick_file()?;
 
     let data = std::fs::read_to_string(import_file_path).ok()?;
-    let mut parsed: SerializableState = serde_json::from_str(&data).ok()?;
+    let mut parsed = SerializableState::from_json(&data).ok()?;
 
-    if let Some(github_pat) = parsed.github_pat {
-        let decoded_pat = decode(&github_pat);
-        parsed.github_pat = Some(decoded_pat);
-    }
+    decrypt_secrets(&mut parsed);
 
     Some(parsed)
 }
+
+/*
+ * Picking the folder used by the local folder sync backend
+ */
+
+pub fn pick_folder() -> Option<String> {
+    let folder = rfd::FileDialog::new()
+        .set_title("Select a folder to sync through")
+        .pick_folder()?;
+
+    Some(folder.to_string_lossy().to_string())
+}
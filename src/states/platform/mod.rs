@@ -1,8 +1,12 @@
+mod backup;
 mod config;
 mod persistence;
 
 use config::STORAGE_KEY;
 
+pub use backup::{backup_now, list_backups, load_backup, DEFAULT_BACKUP_COUNT};
+pub use persistence::{load_state, set_aside_damaged, sleep};
+
 /*
  * Web
  */
@@ -11,7 +15,7 @@ use config::STORAGE_KEY;
 mod web;
 
 #[cfg(target_arch = "wasm32")]
-pub use web::import_data;
+pub use web::{import_data, on_online, on_page_hide};
 
 /*
  * Android
@@ -45,3 +49,6 @@ use desktop::state_file_path;
 
 #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
 pub use desktop::import_data;
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+pub use desktop::pick_folder;
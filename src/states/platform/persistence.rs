@@ -1,19 +1,25 @@
+use super::backup::rotate_backups;
 use super::STORAGE_KEY;
 
 #[cfg(not(target_arch = "wasm32"))]
 use super::state_file_path;
 
-use crate::states::{decode, encode};
+use crate::states::LoadError;
 use crate::{AppState, SerializableState};
+use stay_ahead_core::encoding::KEY_LEN;
+use stay_ahead_core::state_file;
+use std::sync::OnceLock;
 
-#[derive(thiserror::Error, Debug)]
-pub enum LoadError {
-    #[error("Failed to read state file: {0}")]
-    FileNotFound(#[from] std::io::Error),
-
-    #[error("Failed to parse JSON state: {0}")]
-    InvalidJson(#[from] serde_json::Error),
-}
+#[cfg(not(target_arch = "wasm32"))]
+use crate::states::MyTask;
+#[cfg(not(target_arch = "wasm32"))]
+use dioxus::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use stay_ahead_core::merge::{merge_tasks, SyncBase};
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Mutex;
 
 /*
  * Methods for persistent app state
@@ -22,72 +28,307 @@ pub enum LoadError {
  * Use cache based method for web target
  */
 
+// What the state file held when the app last read or wrote it, and what was synced in it
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+struct OnDisk {
+    digest: Option<String>,
+    tasks: HashMap<i64, MyTask>,
+    sync_base: Option<SyncBase>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+static ON_DISK: Mutex<Option<OnDisk>> = Mutex::new(None);
+
 #[cfg(not(target_arch = "wasm32"))]
 impl AppState {
-    pub fn save(&self) {
+    // Returns the content hash of what was saved. It differs from the one before saving when
+    // changes made elsewhere were merged in, so they are not taken for new changes to save.
+    pub fn save(&self) -> u64 {
+        let path = state_file_path();
+
+        self.merge_changes_on_disk(&path);
+
         let mut serializable: SerializableState = self.into();
 
-        if let Some(github_pat) = serializable.github_pat {
-            let encrypted_pat = encode(&github_pat);
-            serializable.github_pat = Some(encrypted_pat);
+        encrypt_secrets(&mut serializable);
+
+        let json = match serde_json::to_string_pretty(&serializable) {
+            Ok(json) => json,
+            Err(e) => {
+                log::error!("Failed to serialize the app state: {}", e);
+                return self.content_hash();
+            }
+        };
+
+        rotate_backups((self.backup_count)());
+
+        match state_file::write_atomically(&path, &json) {
+            Ok(()) => remember_on_disk(OnDisk {
+                digest: Some(state_file::digest(&json)),
+                tasks: (self.tasks)().unwrap_or_default(),
+                sync_base: (self.sync_base)(),
+            }),
+            Err(e) => log::error!("{}", e),
+        }
+
+        self.content_hash()
+    }
+
+    // The command line may have changed the file since the app last read or wrote it.
+    // Its changes are merged like a pull, with what the app last saw there as the base.
+    fn merge_changes_on_disk(&self, path: &std::path::Path) {
+        let Some((base_tasks, base_sync_base)) =
+            ON_DISK
+                .lock()
+                .ok()
+                .and_then(|on_disk| match on_disk.as_ref() {
+                    Some(on_disk) if state_file::digest_on_disk(path) == on_disk.digest => None,
+                    Some(on_disk) => Some((on_disk.tasks.clone(), on_disk.sync_base.clone())),
+                    // The file was missing at start, anything there now was written elsewhere
+                    None => state_file::digest_on_disk(path).map(|_| (HashMap::new(), None)),
+                })
+        else {
+            return;
+        };
+
+        let mut written_elsewhere = match state_file::load(path) {
+            Ok(state) => state,
+            Err(e) => return log::error!("The saved data was changed elsewhere: {}", e),
+        };
+
+        let Some(tasks_elsewhere) = written_elsewhere.read_tasks() else {
+            return;
+        };
+
+        let local_tasks = (self.tasks)().unwrap_or_default();
+        let outcome = merge_tasks(&base_tasks, &local_tasks, &tasks_elsewhere);
+
+        // Both versions were changed, the one in the app is kept
+        for conflict in &outcome.conflicts {
+            log::warn!(
+                "Task {} was changed both in the app and elsewhere, keeping the app's version",
+                conflict.task_id
+            );
         }
 
-        if let Ok(json) = serde_json::to_string_pretty(&serializable) {
-            let _ = std::fs::write(state_file_path(), json);
+        let mut tasks = self.tasks;
+        if outcome.tasks != local_tasks {
+            tasks.set(Some(outcome.tasks));
+        }
+
+        // A push from the command line moved the sync target on, so the next sync merges against
+        // what it pushed. A sync from the app since then has its own base, which is kept.
+        let mut sync_base = self.sync_base;
+        if written_elsewhere.sync_base != base_sync_base && *sync_base.peek() == base_sync_base {
+            sync_base.set(written_elsewhere.sync_base);
+        }
+
+        // Changes logged from the command line still have to be pushed
+        let mut sync_outbox = self.sync_outbox;
+        for change in written_elsewhere.sync_outbox {
+            if !sync_outbox.peek().contains(&change) {
+                sync_outbox.write().push(change);
+            }
         }
     }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-impl SerializableState {
-    pub fn load() -> Result<Self, LoadError> {
-        let data = std::fs::read_to_string(state_file_path())?;
-        let mut parsed = serde_json::from_str::<SerializableState>(&data)?;
-
-        if let Some(github_pat) = parsed.github_pat {
-            let decoded_pat = decode(&github_pat);
-            parsed.github_pat = Some(decoded_pat);
-        }
-
-        Ok(parsed)
+fn remember_on_disk(state: OnDisk) {
+    if let Ok(mut on_disk) = ON_DISK.lock() {
+        *on_disk = Some(state);
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_state() -> Result<SerializableState, LoadError> {
+    let (state, digest) = state_file::load_with_digest(&state_file_path())?;
+
+    remember_on_disk(OnDisk {
+        digest: Some(digest),
+        tasks: state.clone().read_tasks().unwrap_or_default(),
+        sync_base: state.sync_base.clone(),
+    });
+
+    Ok(state)
+}
+
 #[cfg(target_arch = "wasm32")]
 impl AppState {
-    pub fn save(&self) {
+    pub fn save(&self) -> u64 {
+        use super::backup::drop_backups;
         use gloo_storage::{LocalStorage, Storage};
         let mut serializable: SerializableState = self.into();
 
-        if let Some(github_pat) = serializable.github_pat {
-            let encrypted_pat = encode(&github_pat);
-            serializable.github_pat = Some(encrypted_pat);
+        encrypt_secrets(&mut serializable);
+
+        rotate_backups((self.backup_count)());
+
+        if let Err(e) = LocalStorage::set(STORAGE_KEY, &serializable) {
+            log::warn!(
+                "Failed to save to LocalStorage, retrying without backups: {}",
+                e
+            );
+            drop_backups();
+
+            if let Err(e) = LocalStorage::set(STORAGE_KEY, &serializable) {
+                log::error!("Failed to save to LocalStorage: {}", e);
+            }
         }
 
-        let _ = LocalStorage::set(STORAGE_KEY, &serializable);
+        self.content_hash()
     }
 }
 
 #[cfg(target_arch = "wasm32")]
-impl SerializableState {
-    pub fn load() -> Result<Self, LoadError> {
-        use gloo_storage::{LocalStorage, Storage};
+pub fn load_state() -> Result<SerializableState, LoadError> {
+    use gloo_storage::{LocalStorage, Storage};
 
-        let mut parsed: Result<SerializableState, _> =
-            LocalStorage::get(STORAGE_KEY).map_err(|e| {
-                LoadError::FileNotFound(std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    format!("LocalStorage error: {}", e),
-                ))
-            });
-
-        if let Ok(ref mut parsed) = parsed {
-            if let Some(github_pat) = &parsed.github_pat {
-                let decoded_pat = decode(&github_pat);
-                parsed.github_pat = Some(decoded_pat);
-            }
+    use gloo_storage::errors::StorageError;
+
+    let value: serde_json::Value = LocalStorage::get(STORAGE_KEY).map_err(|e| match e {
+        StorageError::SerdeError(e) => LoadError::InvalidJson(e),
+        e => LoadError::FileNotFound(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("LocalStorage error: {}", e),
+        )),
+    })?;
+
+    let mut parsed = SerializableState::from_value(value)?;
+
+    decrypt_secrets(&mut parsed);
+
+    Ok(parsed)
+}
+
+/*
+ * Helpers for coalescing saves
+ */
+
+impl AppState {
+    // Changes whenever anything that gets saved changes, without the cost of writing it
+    pub fn content_hash(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+
+        let serializable: SerializableState = self.into();
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        serde_json::to_string(&serializable)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn sleep(duration: std::time::Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn sleep(duration: std::time::Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
+
+/*
+ * Damaged saved data is moved aside instead of being overwritten
+ */
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn set_aside_damaged() -> Result<String, String> {
+    let path = state_file_path();
+    let damaged = path.with_extension(format!(
+        "damaged-{}.json",
+        chrono::Local::now().format("%Y%m%d%H%M%S")
+    ));
+
+    std::fs::rename(&path, &damaged)
+        .map_err(|e| format!("Failed to move {}: {}", path.display(), e))?;
+
+    Ok(damaged.display().to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn set_aside_damaged() -> Result<String, String> {
+    use gloo_storage::{LocalStorage, Storage};
+
+    let storage = LocalStorage::raw();
+    let damaged_key = format!(
+        "{}_Damaged_{}",
+        STORAGE_KEY,
+        chrono::Local::now().format("%Y%m%d%H%M%S")
+    );
+
+    if let Ok(Some(data)) = storage.get_item(STORAGE_KEY) {
+        storage
+            .set_item(&damaged_key, &data)
+            .map_err(|_| "Failed to keep a copy of the damaged data".to_string())?;
+    }
+
+    LocalStorage::delete(STORAGE_KEY);
+
+    Ok(damaged_key)
+}
+
+/*
+ * Device-local key used to encrypt secrets at rest
+ */
+
+static DEVICE_KEY: OnceLock<[u8; KEY_LEN]> = OnceLock::new();
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn device_key() -> Result<[u8; KEY_LEN], String> {
+    if let Some(key) = DEVICE_KEY.get() {
+        return Ok(*key);
+    }
+
+    let key = state_file::device_key(&state_file_path())?;
+
+    Ok(*DEVICE_KEY.get_or_init(|| key))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn device_key() -> Result<[u8; KEY_LEN], String> {
+    use gloo_storage::{LocalStorage, Storage};
+
+    if let Some(key) = DEVICE_KEY.get() {
+        return Ok(*key);
+    }
+
+    let storage_key = format!("{}_DeviceKey", STORAGE_KEY);
+
+    let key = match LocalStorage::get::<String>(&storage_key)
+        .ok()
+        .and_then(|encoded| state_file::parse_device_key(&encoded))
+    {
+        Some(key) => key,
+        None => {
+            let key = stay_ahead_core::encoding::generate_key()?;
+            LocalStorage::set(&storage_key, state_file::encode_device_key(&key))
+                .map_err(|e| format!("Failed to store the device key: {}", e))?;
+            key
+        }
+    };
+
+    Ok(*DEVICE_KEY.get_or_init(|| key))
+}
+
+pub fn encrypt_secrets(state: &mut SerializableState) {
+    match device_key() {
+        Ok(key) => state.encrypt_secrets(&key),
+        Err(e) => {
+            log::error!("Secrets are not saved: {}", e);
+            state.strip_secrets();
         }
+    }
+}
 
-        parsed
+pub fn decrypt_secrets(state: &mut SerializableState) {
+    match device_key() {
+        Ok(key) => state.decrypt_secrets(&key),
+        Err(e) => {
+            log::error!("Secrets cannot be read: {}", e);
+            state.strip_secrets();
+        }
     }
 }
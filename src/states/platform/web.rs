@@ -1,5 +1,5 @@
+use super::persistence::decrypt_secrets;
 use super::STORAGE_KEY;
-use crate::states::{decode, encode};
 use crate::{AppState, SerializableState};
 use std::path::PathBuf;
 
@@ -17,10 +17,8 @@ impl AppState {
 
         let mut serializable: SerializableState = self.into();
 
-        if let Some(github_pat) = serializable.github_pat {
-            let encrypted_pat = encode(&github_pat);
-            serializable.github_pat = Some(encrypted_pat);
-        }
+        // Secrets stay on this device
+        serializable.strip_secrets();
 
         if let Ok(json) = serde_json::to_string_pretty(&serializable) {
             let data_array = Array::new();
@@ -93,12 +91,9 @@ where
                     let mut on_success = on_success.take().unwrap();
                     let _reader = read_as_text(&file, move |res| {
                         if let Ok(text) = res {
-                            if let Ok(mut parsed) = serde_json::from_str::<SerializableState>(&text)
+                            if let Ok(mut parsed) = SerializableState::from_json(&text)
                             {
-                                if let Some(github_pat) = parsed.github_pat {
-                                    let decoded_pat = decode(&github_pat);
-                                    parsed.github_pat = Some(decoded_pat);
-                                }
+                                decrypt_secrets(&mut parsed);
 
                                 on_success(parsed);
                             }
@@ -116,3 +111,48 @@ where
     onchange.forget();
     input.click();
 }
+
+/*
+ * Last chance to save before the page goes away
+ */
+
+pub fn on_page_hide<F>(callback: F)
+where
+    F: 'static + FnMut(),
+{
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast;
+    use web_sys::window;
+
+    let on_page_hide = Closure::<dyn FnMut()>::new(callback);
+
+    if let Some(window) = window() {
+        let _ = window
+            .add_event_listener_with_callback("pagehide", on_page_hide.as_ref().unchecked_ref());
+    }
+
+    // Lives as long as the page does
+    on_page_hide.forget();
+}
+
+/*
+ * The browser tells us when the network is back, so pending changes can be pushed
+ */
+
+pub fn on_online<F>(callback: F)
+where
+    F: 'static + FnMut(),
+{
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast;
+    use web_sys::window;
+
+    let on_online = Closure::<dyn FnMut()>::new(callback);
+
+    if let Some(window) = window() {
+        let _ = window.add_event_listener_with_callback("online", on_online.as_ref().unchecked_ref());
+    }
+
+    // Lives as long as the page does
+    on_online.forget();
+}
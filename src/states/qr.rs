@@ -22,3 +22,28 @@ pub fn generate_qr_data_url(data: String) -> Option<String> {
     let encoded = general_purpose::STANDARD.encode(&buffer);
     Some(format!("data:image/bmp;base64,{}", encoded))
 }
+
+// Reads the first QR code found in a photo or screenshot
+pub fn decode_qr_image(bytes: &[u8]) -> Result<String, String> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|e| format!("❌ Failed to read the image: {}", e))?
+        .to_luma8();
+
+    let mut prepared = rqrr::PreparedImage::prepare_from_greyscale(
+        image.width() as usize,
+        image.height() as usize,
+        |x, y| image.get_pixel(x as u32, y as u32)[0],
+    );
+
+    let grid = prepared
+        .detect_grids()
+        .into_iter()
+        .next()
+        .ok_or_else(|| "❌ No QR code found in the image".to_string())?;
+
+    let (_, content) = grid
+        .decode()
+        .map_err(|e| format!("❌ Failed to read the QR code: {}", e))?;
+
+    Ok(content)
+}
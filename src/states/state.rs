@@ -1,7 +1,18 @@
+use super::platform::DEFAULT_BACKUP_COUNT;
+use super::sync_coordinator::SyncLogEntry;
 use super::sync_mode::SyncMode;
-use super::{MyTask, SerializableState};
-use crate::states::{decode, encode};
 use dioxus::prelude::*;
+use stay_ahead_core::migration::SCHEMA_VERSION;
+use stay_ahead_core::outbox::PendingChange;
+use stay_ahead_core::schedule::DEFAULT_PACE_WARNING_MULTIPLE;
+use stay_ahead_core::sync::{self, sync_payload, LocalState};
+use stay_ahead_core::sync_backend::{
+    FolderConfig, FolderSync, GistCandidate, GistConfig, S3Config, SyncBackend, SyncBackendKind,
+    SyncError, WebDavConfig,
+};
+use stay_ahead_core::{
+    MyTask, Pairing, QuarantinedTask, SerializableState, SyncBase, SyncConflict,
+};
 use std::collections::HashMap;
 
 /*
@@ -10,193 +21,250 @@ use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 pub struct NoSaveAppState {
-    pub sync_msg: Signal<String>,
     pub sync_mode: Signal<SyncMode>,
-    pub fire_push_after_deletion: Signal<bool>,
+    pub sync_conflicts: Signal<Vec<SyncConflict>>,
+    pub sync_log: Signal<Vec<SyncLogEntry>>,
+    pub load_error: Signal<Option<String>>,
 }
 
 #[derive(Debug, Clone)]
 pub struct AppState {
     pub tasks: Signal<Option<HashMap<i64, MyTask>>>,
-    pub github_pat: Signal<Option<String>>,
-    pub gist_id: Signal<Option<String>>,
-    pub gist_file_name: Signal<Option<String>>,
+    pub sync_backend: Signal<SyncBackendKind>,
+    pub gist_config: Signal<GistConfig>,
+    pub webdav_config: Signal<WebDavConfig>,
+    pub s3_config: Signal<S3Config>,
+    pub folder_config: Signal<FolderConfig>,
+    pub sync_passphrase: Signal<Option<String>>,
+    pub sync_base: Signal<Option<SyncBase>>,
+    pub quarantine: Signal<Vec<QuarantinedTask>>,
+    pub backup_count: Signal<usize>,
+    pub pace_warning_multiple: Signal<f32>,
+    pub sync_outbox: Signal<Vec<PendingChange>>,
 }
 
 /*
  * Conversion from SerializableState to AppState
  */
 
-impl TryFrom<SerializableState> for AppState {
-    type Error = chrono::ParseError;
-
-    fn try_from(state: SerializableState) -> Result<Self, Self::Error> {
-        if let Some(tasks) = state.tasks.clone() {
-            let tasks = tasks
-                .iter()
-                .map(|t| {
-                    let task = MyTask::try_from(t)
-                        .expect("Conversion failed from SerializableTask to MyTask");
-                    (task.id, task)
-                })
-                .collect();
-
-            Ok(AppState {
-                tasks: Signal::new(Some(tasks)),
-                github_pat: Signal::new(state.github_pat),
-                gist_id: Signal::new(state.gist_id),
-                gist_file_name: Signal::new(state.gist_file_name),
-            })
-        } else {
-            Ok(AppState {
-                tasks: Signal::new(None),
-                github_pat: Signal::new(state.github_pat),
-                gist_id: Signal::new(state.gist_id),
-                gist_file_name: Signal::new(state.gist_file_name),
-            })
+impl From<SerializableState> for AppState {
+    fn from(mut state: SerializableState) -> Self {
+        let tasks = state.read_tasks();
+
+        AppState {
+            tasks: Signal::new(tasks),
+            sync_backend: Signal::new(state.sync_backend),
+            gist_config: Signal::new(state.gist),
+            webdav_config: Signal::new(state.webdav),
+            s3_config: Signal::new(state.s3),
+            folder_config: Signal::new(state.folder),
+            sync_passphrase: Signal::new(state.sync_passphrase),
+            sync_base: Signal::new(state.sync_base),
+            quarantine: Signal::new(state.quarantine),
+            backup_count: Signal::new(state.backup_count.unwrap_or(DEFAULT_BACKUP_COUNT)),
+            pace_warning_multiple: Signal::new(
+                state
+                    .pace_warning_multiple
+                    .unwrap_or(DEFAULT_PACE_WARNING_MULTIPLE),
+            ),
+            sync_outbox: Signal::new(state.sync_outbox),
         }
     }
 }
 
 /*
- * AppState sync methods
+ * Conversion from AppState to SerializableState
  */
 
-impl AppState {
-    pub async fn pull(&mut self) -> Result<(), String> {
-        let mut tasks = self.tasks;
-        let mut gist_id = self.gist_id;
-        let mut github_pat = self.github_pat;
-        let mut gist_file_name = self.gist_file_name;
+impl From<&AppState> for SerializableState {
+    fn from(state: &AppState) -> Self {
+        SerializableState {
+            schema_version: SCHEMA_VERSION,
+            tasks: (state.tasks)().map(|tasks| SerializableState::serialize_tasks(&tasks)),
+            sync_backend: (state.sync_backend)(),
+            gist: (state.gist_config)(),
+            webdav: (state.webdav_config)(),
+            s3: (state.s3_config)(),
+            folder: (state.folder_config)(),
+            sync_passphrase: (state.sync_passphrase)(),
+            backup_count: Some((state.backup_count)()),
+            pace_warning_multiple: Some((state.pace_warning_multiple)()),
+            sync_base: (state.sync_base)(),
+            quarantine: (state.quarantine)(),
+            sync_outbox: (state.sync_outbox)(),
+        }
+    }
+}
 
-        let content = pull_from_gist(github_pat(), gist_id(), gist_file_name())
-            .await
-            .map_err(|e| format!("❌ Failed to pull from Gist: {}", e.to_string()))?;
+/*
+ * AppState sync methods
+ */
 
-        let parsed: SerializableState = serde_json::from_str(&content)
-            .map_err(|e| format!("❌ Failed to parse JSON: {}", e))?;
+impl AppState {
+    pub fn sync_configured(&self) -> bool {
+        match (self.sync_backend)() {
+            SyncBackendKind::Gist => (self.gist_config)().is_configured(),
+            SyncBackendKind::WebDav => (self.webdav_config)().is_configured(),
+            SyncBackendKind::S3 => (self.s3_config)().is_configured(),
+            SyncBackendKind::Folder => self.folder_sync().is_configured(),
+        }
+    }
 
-        let state: AppState = parsed
-            .try_into()
-            .map_err(|e| format!("❌ Failed to convert to AppState: {}", e))?;
+    pub async fn pull(&mut self) -> Result<Vec<SyncConflict>, String> {
+        match (self.sync_backend)() {
+            SyncBackendKind::Gist => {
+                let config = (self.gist_config)();
+                sync::pull(self, &config).await
+            }
+            SyncBackendKind::WebDav => {
+                let config = (self.webdav_config)();
+                sync::pull(self, &config).await
+            }
+            SyncBackendKind::S3 => {
+                let config = (self.s3_config)();
+                sync::pull(self, &config).await
+            }
+            SyncBackendKind::Folder => {
+                let config = self.folder_sync();
+                sync::pull(self, &config).await
+            }
+        }
+    }
 
-        tasks.set((state.tasks)());
-        gist_id.set((state.gist_id)());
-        gist_file_name.set((state.gist_file_name)());
+    pub async fn push(&mut self) -> Result<Vec<SyncConflict>, SyncError> {
+        match (self.sync_backend)() {
+            SyncBackendKind::Gist => {
+                let config = (self.gist_config)();
+                sync::push(self, &config).await
+            }
+            SyncBackendKind::WebDav => {
+                let config = (self.webdav_config)();
+                sync::push(self, &config).await
+            }
+            SyncBackendKind::S3 => {
+                let config = (self.s3_config)();
+                sync::push(self, &config).await
+            }
+            SyncBackendKind::Folder => {
+                let config = self.folder_sync();
+                sync::push(self, &config).await
+            }
+        }
+    }
 
-        if let Some(encoded_pat) = (state.github_pat)() {
-            let decoded_pat = decode(&encoded_pat);
-            github_pat.set(Some(decoded_pat));
+    fn folder_sync(&self) -> FolderSync {
+        FolderSync {
+            config: (self.folder_config)(),
+            passphrase: (self.sync_passphrase)(),
+            base_revision: (self.sync_base)().map(|base| base.revision),
         }
+    }
+}
 
-        Ok(())
+/*
+ * What the sync protocol reads and writes
+ */
+
+impl LocalState for AppState {
+    fn get_tasks(&self) -> Option<HashMap<i64, MyTask>> {
+        (self.tasks)()
     }
 
-    pub async fn push(&mut self) -> Result<(), String> {
-        let mut serializable: SerializableState = (&*self).into();
+    fn set_tasks(&mut self, tasks: Option<HashMap<i64, MyTask>>) {
+        self.tasks.set(tasks);
+    }
 
-        let github_pat =
-            (self.github_pat)().ok_or_else(|| "❌ GitHub PAT is missing".to_string())?;
-        let encrypted_pat = encode(&github_pat);
-        serializable.github_pat = Some(encrypted_pat);
+    fn get_sync_base(&self) -> Option<SyncBase> {
+        (self.sync_base)()
+    }
 
-        let json = serde_json::to_string_pretty(&serializable)
-            .map_err(|e| format!("❌ Serialization failed: {}", e))?;
+    fn set_sync_base(&mut self, sync_base: Option<SyncBase>) {
+        self.sync_base.set(sync_base);
+    }
 
-        let github_pat = (self.github_pat)();
-        let gist_id = (self.gist_id)();
-        let gist_file_name = (self.gist_file_name)();
+    fn passphrase(&self) -> Option<String> {
+        (self.sync_passphrase)()
+    }
 
-        push_to_gist(github_pat, gist_id, gist_file_name, json).await
+    fn to_serializable(&self) -> SerializableState {
+        self.into()
     }
 }
 
-async fn pull_from_gist(
-    github_pat: Option<String>,
-    gist_id: Option<String>,
-    gist_file_name: Option<String>,
-) -> Result<String, Box<dyn std::error::Error + 'static>> {
-    if github_pat.is_none() || gist_id.is_none() || gist_file_name.is_none() {
-        return Err("Incomplete configuration for pulling".into());
-    }
+/*
+ * Gist setup from a PAT, and restoring from its history
+ */
+
+impl AppState {
+    // The new gist starts out with the current state, so it is already in sync
+    pub async fn create_sync_gist(&mut self) -> Result<(), String> {
+        let (json, tasks) = sync_payload(self)?;
 
-    let github_pat = github_pat.unwrap();
-    let gist_id = gist_id.unwrap();
-    let gist_file_name = gist_file_name.unwrap();
+        let (config, revision) = (self.gist_config)()
+            .create_gist(json)
+            .await
+            .map_err(|e| format!("❌ {}", e))?;
+
+        self.gist_config.set(config);
+        self.sync_base.set(Some(SyncBase { revision, tasks }));
 
-    let client = reqwest::Client::new();
-    let get_url = format!("https://api.github.com/gists/{}", gist_id);
+        Ok(())
+    }
 
-    let response = client
-        .get(&get_url)
-        .header("Authorization", format!("Bearer {}", github_pat))
-        .header("User-Agent", "Stay Ahead - Palegg Works")
-        .send()
-        .await?;
+    pub async fn gist_revision(&self, revision: &str) -> Result<SerializableState, String> {
+        let remote = (self.gist_config)()
+            .fetch_revision(revision)
+            .await
+            .map_err(|e| format!("❌ Failed to fetch the revision: {}", e))?;
 
-    if !response.status().is_success() {
-        return Err(format!("GitHub API request failed: {}", response.status()).into());
+        sync::read_remote(&remote.content, (self.sync_passphrase)().as_deref())
     }
 
-    let gist: serde_json::Value = response.json().await?;
+    // Past tasks replace the ones with the same ID, or every task when `whole` is set
+    pub fn restore_tasks(&mut self, tasks: Vec<MyTask>, whole: bool) {
+        let mut tasks_guard = self.tasks.write();
+        let current = tasks_guard.get_or_insert_with(Default::default);
+
+        if whole {
+            current.clear();
+        }
+
+        for task in tasks {
+            current.insert(task.id, task);
+        }
+    }
 
-    let content = gist["files"][gist_file_name]["content"]
-        .as_str()
-        .unwrap_or_default()
-        .to_string();
+    // Nothing is known about what the other gist holds, so the next sync merges it in full
+    pub fn use_gist(&mut self, candidate: &GistCandidate) {
+        let mut gist_config = self.gist_config.write();
+        gist_config.gist_id = Some(candidate.gist_id.clone());
+        gist_config.gist_file_name = Some(candidate.file_name.clone());
 
-    Ok(content)
+        self.sync_base.set(None);
+    }
 }
 
-async fn push_to_gist(
-    github_pat: Option<String>,
-    gist_id: Option<String>,
-    gist_file_name: Option<String>,
-    new_content: String,
-) -> Result<(), String> {
-    if github_pat.is_none() || gist_id.is_none() || gist_file_name.is_none() {
-        return Err("Incomplete configuration for pushing".to_string());
-    }
-
-    let github_pat = github_pat.unwrap();
-    let gist_id = gist_id.unwrap();
-    let gist_file_name = gist_file_name.unwrap();
-
-    let client = reqwest::Client::new();
-    let get_url = format!("https://api.github.com/gists/{}", gist_id);
-
-    let mut files = serde_json::Map::new();
-    files.insert(
-        gist_file_name.to_string(),
-        serde_json::json!({ "content": new_content }),
-    );
-
-    let patch_body = serde_json::json!({ "files": files });
-
-    let response = client
-        .patch(&get_url)
-        .header("Authorization", format!("Bearer {}", github_pat))
-        .header("User-Agent", "Stay Ahead - Palegg Works")
-        .json(&patch_body)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send request: {e}"))?;
-
-    if response.status().is_success() {
+/*
+ * Device pairing
+ */
+
+impl AppState {
+    // Like switching gists, the next sync merges whatever the paired device has pushed
+    pub fn apply_pairing(&mut self, pairing: Pairing) -> Result<(), String> {
+        if !pairing.sync_backend.is_available() {
+            return Err(format!(
+                "❌ {} sync is not available on this device",
+                pairing.sync_backend
+            ));
+        }
+
+        self.sync_backend.set(pairing.sync_backend);
+        self.gist_config.set(pairing.gist);
+        self.webdav_config.set(pairing.webdav);
+        self.s3_config.set(pairing.s3);
+        self.sync_passphrase.set(pairing.sync_passphrase);
+        self.sync_base.set(None);
+
         Ok(())
-    } else {
-        let reset_time = response
-            .headers()
-            .get("x-ratelimit-reset")
-            .and_then(|val| val.to_str().ok())
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| "unknown".to_string());
-
-        let status = response.status();
-        let body = response.text().await.unwrap_or_default();
-
-        Err(format!(
-            "Push error: {status} - {body}\n⚠️ Retry after: x-ratelimit-reset = {reset_time}"
-        ))
     }
 }
@@ -0,0 +1,254 @@
+use super::platform::sleep;
+use super::state::{AppState, NoSaveAppState};
+use super::sync_mode::SyncMode;
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use dioxus::prelude::*;
+use stay_ahead_core::log_entry::TIMESTAMP_FORMAT;
+use stay_ahead_core::outbox::{Backoff, PendingChange};
+use stay_ahead_core::SyncConflict;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+// Edits made within this window after a push was requested end up in that push
+const PUSH_COALESCE: Duration = Duration::from_millis(1500);
+
+const SYNC_LOG_LEN: usize = 50;
+
+/*
+ * Struct Definition
+ */
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncAction {
+    Push,
+    Pull,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncLogEntry {
+    pub timestamp: NaiveDateTime,
+    pub action: SyncAction,
+    pub reasons: Vec<String>,
+
+    // The number of new conflicts, or why it failed
+    pub result: Result<usize, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct QueuedSync {
+    action: SyncAction,
+    reasons: Vec<String>,
+}
+
+// Everything is a signal handle, so the coordinator can be copied into any closure
+#[derive(Debug, Clone, Copy)]
+pub struct SyncCoordinator {
+    app_state: CopyValue<AppState>,
+    sync_mode: Signal<SyncMode>,
+    sync_conflicts: Signal<Vec<SyncConflict>>,
+    sync_log: Signal<Vec<SyncLogEntry>>,
+    queue: Signal<VecDeque<QueuedSync>>,
+    running: Signal<bool>,
+    backoff: Signal<Backoff>,
+}
+
+/*
+ * Every push and pull goes through here
+ *
+ * Requests are queued and run one at a time, so a push and a pull never
+ * overlap. Consecutive requests of the same kind are merged into one, and a
+ * push waits a moment before it starts so that rapid edits share it.
+ *
+ * Changes are kept in the outbox of the app state until a push gets them to
+ * the sync target. A failed push is retried with exponential backoff, and
+ * again on the next start, when the network comes back on the web, or when
+ * the app is focused or resumed elsewhere. A push refused
+ * because tasks on this device could not be read is not retried, since only
+ * exporting or discarding them helps.
+ */
+
+impl SyncCoordinator {
+    pub fn new(app_state: AppState, no_save_app_state: &NoSaveAppState) -> Self {
+        Self {
+            app_state: CopyValue::new(app_state),
+            sync_mode: no_save_app_state.sync_mode,
+            sync_conflicts: no_save_app_state.sync_conflicts,
+            sync_log: no_save_app_state.sync_log,
+            queue: Signal::new(VecDeque::new()),
+            running: Signal::new(false),
+            backoff: Signal::new(Backoff::default()),
+        }
+    }
+
+    // For a local change that should reach the other devices
+    pub fn request_push(&self, reason: &str) {
+        let app_state = self.app_state.cloned();
+
+        if app_state.sync_configured() {
+            let mut sync_outbox = app_state.sync_outbox;
+            sync_outbox.write().push(PendingChange {
+                reason: reason.to_string(),
+                requested: Local::now().format(TIMESTAMP_FORMAT).to_string(),
+            });
+        }
+
+        self.enqueue(SyncAction::Push, reason);
+    }
+
+    // Pushes right away even while waiting to retry, unless a rate limit is not over yet
+    pub fn push_now(&self, reason: &str) {
+        let mut backoff = self.backoff;
+        backoff.write().lift();
+
+        self.enqueue(SyncAction::Push, reason);
+    }
+
+    pub fn retry_pending(&self, reason: &str) {
+        if !self.app_state.read().sync_outbox.peek().is_empty() {
+            self.push_now(reason);
+        }
+    }
+
+    pub fn request_pull(&self, reason: &str) {
+        self.enqueue(SyncAction::Pull, reason);
+    }
+
+    fn enqueue(&self, action: SyncAction, reason: &str) {
+        let mut queue = self.queue;
+
+        {
+            let mut queue = queue.write();
+            match queue.back_mut() {
+                Some(last) if last.action == action => last.reasons.push(reason.to_string()),
+                _ => queue.push_back(QueuedSync {
+                    action,
+                    reasons: vec![reason.to_string()],
+                }),
+            }
+        }
+
+        if !*self.running.peek() {
+            let mut running = self.running;
+            running.set(true);
+
+            // Not tied to the page that asked, which may be gone before it finishes
+            spawn_forever(self.run());
+        }
+    }
+
+    async fn run(mut self) {
+        let mut app_state = self.app_state.cloned();
+
+        loop {
+            let Some(action) = self.queue.peek().front().map(|job| job.action) else {
+                break;
+            };
+
+            if action == SyncAction::Push {
+                sleep(PUSH_COALESCE).await;
+            }
+
+            let Some(job) = self.queue.write().pop_front() else {
+                break;
+            };
+
+            if !app_state.sync_configured() {
+                // There is nowhere to push to anymore
+                app_state.sync_outbox.set(vec![]);
+                self.sync_mode.set(SyncMode::NotSynced);
+                continue;
+            }
+
+            // The retry that is already scheduled takes these changes along
+            let wait = self.backoff.peek().wait();
+            if let (SyncAction::Push, Some(wait)) = (job.action, wait) {
+                let retry_at = Local::now() + chrono::Duration::from_std(wait).unwrap_or_default();
+
+                self.log(SyncLogEntry {
+                    timestamp: Local::now().naive_local(),
+                    action: job.action,
+                    reasons: job.reasons,
+                    result: Err(format!(
+                        "Not pushed yet, waiting to retry at {}",
+                        retry_at.format("%H:%M:%S")
+                    )),
+                });
+                continue;
+            }
+
+            // Changes made while pushing get their own push
+            let n_pending = app_state.sync_outbox.peek().len();
+
+            self.sync_mode.set(match job.action {
+                SyncAction::Push => SyncMode::Pushing,
+                SyncAction::Pull => SyncMode::Pulling,
+            });
+
+            let result = match job.action {
+                SyncAction::Push => match app_state.push().await {
+                    Ok(conflicts) => {
+                        self.backoff.write().succeeded();
+
+                        let mut sync_outbox = app_state.sync_outbox.write();
+                        let n_pushed = n_pending.min(sync_outbox.len());
+                        sync_outbox.drain(..n_pushed);
+
+                        Ok(conflicts)
+                    }
+                    Err(e) if !app_state.quarantine.peek().is_empty() => Err(e.to_string()),
+                    Err(e) => {
+                        let retry_at = self.backoff.write().failed(e.retry_at);
+                        self.schedule_retry(retry_at);
+
+                        Err(format!(
+                            "{} (retrying at {})",
+                            e,
+                            retry_at.with_timezone(&Local).format("%H:%M:%S")
+                        ))
+                    }
+                },
+                SyncAction::Pull => app_state.pull().await,
+            };
+
+            match &result {
+                Ok(conflicts) => {
+                    self.sync_mode.set(SyncMode::InSync);
+
+                    let mut sync_conflicts = self.sync_conflicts.write();
+                    for conflict in conflicts {
+                        sync_conflicts.retain(|c| c.task_id != conflict.task_id);
+                        sync_conflicts.push(conflict.clone());
+                    }
+                }
+                Err(_) => self.sync_mode.set(SyncMode::Failed),
+            }
+
+            self.log(SyncLogEntry {
+                timestamp: Local::now().naive_local(),
+                action: job.action,
+                reasons: job.reasons,
+                result: result.map(|conflicts| conflicts.len()),
+            });
+        }
+
+        self.running.set(false);
+    }
+
+    fn schedule_retry(&self, retry_at: DateTime<Utc>) {
+        let coordinator = *self;
+        let wait = (retry_at - Utc::now()).to_std().unwrap_or_default();
+
+        spawn_forever(async move {
+            sleep(wait).await;
+            coordinator.retry_pending("Retry");
+        });
+    }
+
+    fn log(&mut self, entry: SyncLogEntry) {
+        let mut sync_log = self.sync_log.write();
+        sync_log.push(entry);
+
+        let overflow = sync_log.len().saturating_sub(SYNC_LOG_LEN);
+        sync_log.drain(..overflow);
+    }
+}
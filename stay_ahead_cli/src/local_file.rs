@@ -0,0 +1,288 @@
+use chrono::Local;
+use stay_ahead_core::log_entry::TIMESTAMP_FORMAT;
+use stay_ahead_core::migration::SCHEMA_VERSION;
+use stay_ahead_core::outbox::PendingChange;
+use stay_ahead_core::schedule::DEFAULT_PACE_WARNING_MULTIPLE;
+use stay_ahead_core::sync::{self, LocalState};
+use stay_ahead_core::sync_backend::{FolderSync, SyncBackend, SyncBackendKind, SyncError};
+use stay_ahead_core::{state_file, LoadError, MyTask, SerializableState, SyncBase, SyncConflict};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/*
+ * Struct Definition
+ */
+
+// The state file as read at start, with its tasks ready to be changed
+pub struct LocalFile {
+    path: PathBuf,
+    state: SerializableState,
+    tasks: Option<HashMap<i64, MyTask>>,
+}
+
+impl LocalFile {
+    // A missing file is a fresh start, like on the first launch of the app
+    pub fn open(path: PathBuf) -> Result<Self, String> {
+        let mut state = match state_file::load(&path) {
+            Ok(state) => state,
+            Err(LoadError::FileNotFound(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                SerializableState {
+                    schema_version: SCHEMA_VERSION,
+                    ..Default::default()
+                }
+            }
+            Err(e) => return Err(format!("❌ Failed to read {}: {}", path.display(), e)),
+        };
+
+        let tasks = state.read_tasks();
+
+        Ok(LocalFile { path, state, tasks })
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        state_file::save(&self.path, &self.to_serializable()).map_err(|e| format!("❌ {}", e))
+    }
+}
+
+/*
+ * Finding and changing tasks
+ */
+
+impl LocalFile {
+    // Oldest first, the order they were created in
+    pub fn tasks(&self) -> Vec<&MyTask> {
+        let mut tasks: Vec<&MyTask> = self.tasks.iter().flat_map(|tasks| tasks.values()).collect();
+        tasks.sort_by_key(|task| task.id);
+        tasks
+    }
+
+    // By ID, or by a name or action that only one task has
+    pub fn find_task(&self, query: &str) -> Result<&MyTask, String> {
+        if let Some(task) = query
+            .parse::<i64>()
+            .ok()
+            .and_then(|id| self.tasks.as_ref()?.get(&id))
+        {
+            return Ok(task);
+        }
+
+        let query = query.to_lowercase();
+        let matches: Vec<&MyTask> = self
+            .tasks()
+            .into_iter()
+            .filter(|task| {
+                task.name.as_ref().is_some_and(|name| name.to_lowercase() == query)
+                    || task.action.to_lowercase() == query
+            })
+            .collect();
+
+        match matches.as_slice() {
+            [task] => Ok(task),
+            [] => Err(format!("❌ No task matches \"{}\"", query)),
+            _ => Err(format!(
+                "❌ {} tasks match \"{}\", use the task ID instead",
+                matches.len(),
+                query
+            )),
+        }
+    }
+
+    pub fn task_mut(&mut self, id: i64) -> Option<&mut MyTask> {
+        self.tasks.as_mut()?.get_mut(&id)
+    }
+
+    pub fn insert_task(&mut self, task: MyTask) {
+        self.tasks
+            .get_or_insert_with(Default::default)
+            .insert(task.id, task);
+    }
+
+    // Kept as pending, so the app pushes it on its next start if it is not pushed from here
+    pub fn record_change(&mut self, reason: &str) {
+        if self.sync_configured() {
+            self.state.sync_outbox.push(PendingChange {
+                reason: reason.to_string(),
+                requested: Local::now().format(TIMESTAMP_FORMAT).to_string(),
+            });
+        }
+    }
+
+    pub fn n_pending(&self) -> usize {
+        self.state.sync_outbox.len()
+    }
+
+    pub fn pace_warning_multiple(&self) -> f32 {
+        self.state
+            .pace_warning_multiple
+            .unwrap_or(DEFAULT_PACE_WARNING_MULTIPLE)
+    }
+}
+
+/*
+ * Syncing with the backend picked in the app
+ */
+
+impl LocalFile {
+    pub fn sync_backend(&self) -> SyncBackendKind {
+        self.state.sync_backend
+    }
+
+    pub fn sync_configured(&self) -> bool {
+        match self.state.sync_backend {
+            SyncBackendKind::Gist => self.state.gist.is_configured(),
+            SyncBackendKind::WebDav => self.state.webdav.is_configured(),
+            SyncBackendKind::S3 => self.state.s3.is_configured(),
+            SyncBackendKind::Folder => self.folder_sync().is_configured(),
+        }
+    }
+
+    pub async fn pull(&mut self) -> Result<Vec<SyncConflict>, String> {
+        match self.state.sync_backend {
+            SyncBackendKind::Gist => {
+                let config = self.state.gist.clone();
+                sync::pull(self, &config).await
+            }
+            SyncBackendKind::WebDav => {
+                let config = self.state.webdav.clone();
+                sync::pull(self, &config).await
+            }
+            SyncBackendKind::S3 => {
+                let config = self.state.s3.clone();
+                sync::pull(self, &config).await
+            }
+            SyncBackendKind::Folder => {
+                let config = self.folder_sync();
+                sync::pull(self, &config).await
+            }
+        }
+    }
+
+    // Everything pending is part of a successful push
+    pub async fn push(&mut self) -> Result<Vec<SyncConflict>, SyncError> {
+        let conflicts = match self.state.sync_backend {
+            SyncBackendKind::Gist => {
+                let config = self.state.gist.clone();
+                sync::push(self, &config).await
+            }
+            SyncBackendKind::WebDav => {
+                let config = self.state.webdav.clone();
+                sync::push(self, &config).await
+            }
+            SyncBackendKind::S3 => {
+                let config = self.state.s3.clone();
+                sync::push(self, &config).await
+            }
+            SyncBackendKind::Folder => {
+                let config = self.folder_sync();
+                sync::push(self, &config).await
+            }
+        }?;
+
+        self.state.sync_outbox.clear();
+
+        Ok(conflicts)
+    }
+
+    fn folder_sync(&self) -> FolderSync {
+        FolderSync {
+            config: self.state.folder.clone(),
+            passphrase: self.state.sync_passphrase.clone(),
+            base_revision: self
+                .state
+                .sync_base
+                .as_ref()
+                .map(|base| base.revision.clone()),
+        }
+    }
+}
+
+/*
+ * What the sync protocol reads and writes
+ */
+
+impl LocalState for LocalFile {
+    fn get_tasks(&self) -> Option<HashMap<i64, MyTask>> {
+        self.tasks.clone()
+    }
+
+    fn set_tasks(&mut self, tasks: Option<HashMap<i64, MyTask>>) {
+        self.tasks = tasks;
+    }
+
+    fn get_sync_base(&self) -> Option<SyncBase> {
+        self.state.sync_base.clone()
+    }
+
+    fn set_sync_base(&mut self, sync_base: Option<SyncBase>) {
+        self.state.sync_base = sync_base;
+    }
+
+    fn passphrase(&self) -> Option<String> {
+        self.state.sync_passphrase.clone()
+    }
+
+    fn to_serializable(&self) -> SerializableState {
+        SerializableState {
+            tasks: self.tasks.as_ref().map(SerializableState::serialize_tasks),
+            ..self.state.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use stay_ahead_core::schedule::ALL_WEEKDAYS;
+
+    fn task(id: i64, action: &str, name: Option<&str>) -> MyTask {
+        MyTask {
+            id,
+            action: action.to_string(),
+            count_per_day: 10.0,
+            unit: "pages".to_string(),
+            logs: vec![],
+            start: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            end: NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(),
+            effective_dow: ALL_WEEKDAYS.to_vec(),
+            daily_tasks: None,
+            name: name.map(str::to_string),
+            archive: false,
+        }
+    }
+
+    fn file(tasks: impl IntoIterator<Item = MyTask>) -> LocalFile {
+        LocalFile {
+            path: PathBuf::new(),
+            state: SerializableState::default(),
+            tasks: Some(tasks.into_iter().map(|task| (task.id, task)).collect()),
+        }
+    }
+
+    #[test]
+    fn tasks_are_found_by_id_name_or_action() {
+        let file = file([
+            task(1, "Read", None),
+            task(2, "Complete", Some("Learn Rust")),
+        ]);
+
+        assert_eq!(file.find_task("2").unwrap().id, 2);
+        assert_eq!(file.find_task("learn rust").unwrap().id, 2);
+        assert_eq!(file.find_task("READ").unwrap().id, 1);
+    }
+
+    #[test]
+    fn a_query_matching_several_tasks_or_none_is_an_error() {
+        let file = file([task(1, "Read", None), task(2, "Read", None)]);
+
+        assert_eq!(
+            file.find_task("read").unwrap_err(),
+            "❌ 2 tasks match \"read\", use the task ID instead"
+        );
+        assert_eq!(
+            file.find_task("Run").unwrap_err(),
+            "❌ No task matches \"run\""
+        );
+        assert!(file.find_task("3").is_err());
+    }
+}
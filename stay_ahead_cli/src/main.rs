@@ -0,0 +1,575 @@
+mod local_file;
+mod report;
+
+use chrono::{Local, NaiveDate, Weekday};
+use clap::{Args, Parser, Subcommand};
+use local_file::LocalFile;
+use report::{LoggedEntry, TaskSummary, TaskTimeline};
+use serde::Serialize;
+use stay_ahead_core::log_entry::LogEntry;
+use stay_ahead_core::schedule::{calculate_completion_date, generate_date_range, ALL_WEEKDAYS};
+use stay_ahead_core::serializable_log_entry::SerializableLogEntry;
+use stay_ahead_core::state_file::state_file_in;
+use stay_ahead_core::{MyTask, SyncConflict};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/*
+ * Command line
+ */
+
+/// Log progress and sync Stay Ahead tasks from the terminal.
+///
+/// Reads and writes the desktop app's state file. An open app merges what was
+/// changed here the next time it saves.
+#[derive(Parser)]
+#[command(name = "stay-ahead", version)]
+struct Cli {
+    /// Print JSON instead of text, for scripts
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// State file to use instead of the desktop app's
+    #[arg(long, global = true, value_name = "FILE")]
+    state_file: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List tasks with their progress
+    List {
+        /// Also list archived tasks
+        #[arg(long)]
+        all: bool,
+
+        /// Put the tasks furthest behind the Parallel Universe first
+        #[arg(long)]
+        behind_first: bool,
+    },
+
+    /// Log progress on a task, given by ID, name or action
+    Log {
+        task: String,
+        amount: f32,
+
+        /// Effective day to log on, today by default
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        date: Option<NaiveDate>,
+
+        #[arg(long)]
+        note: Option<String>,
+    },
+
+    /// Show the Parallel Universe and Your Universe timeline of a task
+    Show {
+        task: String,
+
+        /// Leave out the days before today
+        #[arg(long)]
+        from_today: bool,
+    },
+
+    /// Create a task
+    Create(CreateArgs),
+
+    /// Archive a task, or bring it back with --restore
+    Archive {
+        task: String,
+
+        #[arg(long)]
+        restore: bool,
+    },
+
+    /// Push to the sync target set up in the app, merging what is there first
+    Push,
+
+    /// Pull from the sync target set up in the app
+    Pull,
+}
+
+/// Either a habit (--action, --count-per-day, --unit and --end)
+/// or a goal with one activity per day (--name and --daily-tasks)
+#[derive(Args)]
+struct CreateArgs {
+    /// What to do every day, e.g. "Read"
+    #[arg(long, conflicts_with_all = ["name", "daily_tasks"], requires_all = ["count_per_day", "unit", "end"])]
+    action: Option<String>,
+
+    #[arg(long)]
+    count_per_day: Option<f32>,
+
+    /// e.g. "pages"
+    #[arg(long)]
+    unit: Option<String>,
+
+    /// Last day of a habit
+    #[arg(long, value_name = "YYYY-MM-DD")]
+    end: Option<NaiveDate>,
+
+    /// Name of a goal
+    #[arg(long, requires = "daily_tasks")]
+    name: Option<String>,
+
+    /// Text file with one activity per line, one line per effective day
+    #[arg(long, value_name = "FILE", requires = "name")]
+    daily_tasks: Option<PathBuf>,
+
+    /// First day, today by default
+    #[arg(long, value_name = "YYYY-MM-DD")]
+    start: Option<NaiveDate>,
+
+    /// Effective days of the week, e.g. Mon,Wed,Fri. Every day by default
+    #[arg(long, value_delimiter = ',')]
+    days: Vec<Weekday>,
+}
+
+#[derive(Debug, Serialize)]
+struct SyncReport {
+    backend: String,
+    conflicts: Vec<ConflictSummary>,
+}
+
+// `kept` is "local" or "remote", the version that still exists with this device's preferred
+#[derive(Debug, Serialize)]
+struct ConflictSummary {
+    task_id: i64,
+    label: String,
+    kept: String,
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+    let path = cli.state_file.unwrap_or_else(|| {
+        state_file_in(&dirs::data_dir().unwrap_or_else(|| PathBuf::from(".")))
+    });
+    let mut file = LocalFile::open(path)?;
+    let today = Local::now().date_naive();
+    let json = cli.json;
+
+    match cli.command {
+        Command::List { all, behind_first } => {
+            let mut summaries: Vec<TaskSummary> = file
+                .tasks()
+                .into_iter()
+                .filter(|task| all || !task.archive)
+                .map(|task| TaskSummary::new(task, today))
+                .collect();
+
+            if behind_first {
+                summaries.sort_by(|a, b| a.lead_days.total_cmp(&b.lead_days));
+            }
+
+            print(json, &summaries, |summaries| {
+                if summaries.is_empty() {
+                    "No tasks yet".to_string()
+                } else {
+                    summaries
+                        .iter()
+                        .map(TaskSummary::to_text)
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            })
+        }
+
+        Command::Log {
+            task,
+            amount,
+            date,
+            note,
+        } => {
+            let task = file.find_task(&task)?.clone();
+            let date = date.unwrap_or(today);
+            check_log(&task, amount, date, today)?;
+
+            let entry = LogEntry::new(date, amount, note);
+            let logged = LoggedEntry {
+                task_id: task.id,
+                entry: SerializableLogEntry::from(&entry),
+                accomplished: task.count_accum() + amount,
+            };
+
+            if let Some(task) = file.task_mut(task.id) {
+                task.logs.push(entry);
+            }
+            file.record_change("Logged progress from the command line");
+            file.save()?;
+
+            print(json, &logged, |logged| {
+                format!(
+                    "✅ Logged {} {} on {} for \"{}\", {:.1} {} done{}",
+                    amount,
+                    task.unit,
+                    date,
+                    task.label(),
+                    logged.accomplished,
+                    task.unit,
+                    pending_hint(&file)
+                )
+            })
+        }
+
+        Command::Show { task, from_today } => {
+            let timeline = TaskTimeline::new(
+                file.find_task(&task)?,
+                today,
+                from_today,
+                file.pace_warning_multiple(),
+            );
+
+            print(json, &timeline, TaskTimeline::to_text)
+        }
+
+        Command::Create(args) => {
+            let task = new_task(args, today)?;
+            let summary = TaskSummary::new(&task, today);
+
+            file.insert_task(task);
+            file.record_change("Created a task from the command line");
+            file.save()?;
+
+            print(json, &summary, |summary| {
+                format!("✅ Created a task\n{}{}", summary.to_text(), pending_hint(&file))
+            })
+        }
+
+        Command::Archive { task, restore } => {
+            let id = file.find_task(&task)?.id;
+
+            if let Some(task) = file.task_mut(id) {
+                task.archive = !restore;
+            }
+            file.record_change("Changed the visibility of a task from the command line");
+            file.save()?;
+
+            let task = file.find_task(&id.to_string())?;
+            let summary = TaskSummary::new(task, today);
+
+            print(json, &summary, |summary| {
+                format!(
+                    "✅ {} \"{}\"{}",
+                    if restore { "Activated" } else { "Archived" },
+                    summary.label,
+                    pending_hint(&file)
+                )
+            })
+        }
+
+        Command::Push => {
+            if !file.sync_configured() {
+                return Err("❌ Sync is not configured, set it up in the app first".to_string());
+            }
+
+            let conflicts = file.push().await.map_err(|e| format!("❌ {}", e))?;
+            file.save()?;
+
+            let report = sync_report(&file, conflicts);
+            print(json, &report, |report| sync_text("Pushed to", report))
+        }
+
+        Command::Pull => {
+            if !file.sync_configured() {
+                return Err("❌ Sync is not configured, set it up in the app first".to_string());
+            }
+
+            let conflicts = file.pull().await?;
+            file.save()?;
+
+            let report = sync_report(&file, conflicts);
+            print(json, &report, |report| sync_text("Pulled from", report))
+        }
+    }
+}
+
+/*
+ * Helpers
+ */
+
+// Output piped into a command that stops reading early is not an error
+fn print<T: Serialize>(json: bool, value: &T, text: impl FnOnce(&T) -> String) -> Result<(), String> {
+    let output = if json {
+        serde_json::to_string_pretty(value)
+            .map_err(|e| format!("❌ Serialization failed: {}", e))?
+    } else {
+        text(value)
+    };
+
+    let _ = writeln!(std::io::stdout(), "{}", output);
+
+    Ok(())
+}
+
+fn pending_hint(file: &LocalFile) -> String {
+    match file.n_pending() {
+        0 => String::new(),
+        n => format!(
+            "\n{} change(s) not pushed yet, run `stay-ahead push` or open the app",
+            n
+        ),
+    }
+}
+
+// The same days the app offers to log on
+fn check_log(task: &MyTask, amount: f32, date: NaiveDate, today: NaiveDate) -> Result<(), String> {
+    if amount <= 0.0 {
+        return Err("❌ The amount needs to be more than 0".to_string());
+    }
+
+    if !generate_date_range(task.start, task.end.min(today), task.effective_dow.clone())
+        .contains(&date)
+    {
+        return Err(format!(
+            "❌ {} is not an effective day of \"{}\" up to today",
+            date,
+            task.label()
+        ));
+    }
+
+    Ok(())
+}
+
+fn new_task(args: CreateArgs, today: NaiveDate) -> Result<MyTask, String> {
+    let start = args.start.unwrap_or(today);
+
+    let mut effective_dow = if args.days.is_empty() {
+        ALL_WEEKDAYS.to_vec()
+    } else {
+        args.days
+    };
+    effective_dow.sort_by_key(|d| d.num_days_from_monday());
+    effective_dow.dedup();
+
+    let mut task = MyTask {
+        id: chrono::Utc::now().timestamp_millis(),
+        action: String::new(),
+        count_per_day: 0.0,
+        unit: String::new(),
+        logs: vec![],
+        start,
+        end: start,
+        effective_dow,
+        daily_tasks: None,
+        name: None,
+        archive: false,
+    };
+
+    match (args.action, args.name, args.daily_tasks) {
+        (Some(action), _, _) => {
+            let count_per_day = args.count_per_day.unwrap_or_default();
+            let end = args.end.unwrap_or(start);
+
+            if count_per_day <= 0.0 {
+                return Err("❌ --count-per-day needs to be more than 0".to_string());
+            }
+            if end < start {
+                return Err("❌ Task creation failed! Please check dates!".to_string());
+            }
+
+            task.action = action;
+            task.count_per_day = count_per_day;
+            task.unit = args.unit.unwrap_or_default();
+            task.end = end;
+        }
+
+        // Same as a challenge made with "Goal Achiever" in the app
+        (None, Some(name), Some(daily_tasks_path)) => {
+            let content = std::fs::read_to_string(&daily_tasks_path)
+                .map_err(|e| format!("❌ Failed to read {}: {}", daily_tasks_path.display(), e))?;
+            let daily_tasks: Vec<String> = content
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect();
+
+            if daily_tasks.is_empty() {
+                return Err(format!(
+                    "❌ {} has no daily activities, write one per line",
+                    daily_tasks_path.display()
+                ));
+            }
+
+            task.end = calculate_completion_date(daily_tasks.len(), start, task.effective_dow.clone())
+                .ok_or_else(|| "❌ Task creation failed! Please check dates!".to_string())?;
+            task.action = "Complete".to_string();
+            task.count_per_day = 1.0;
+            task.unit = "line of daily activities".to_string();
+            task.daily_tasks = Some(daily_tasks);
+            task.name = Some(name);
+        }
+
+        _ => {
+            return Err(
+                "❌ Give either --action, --count-per-day, --unit and --end, or --name and --daily-tasks"
+                    .to_string(),
+            )
+        }
+    }
+
+    Ok(task)
+}
+
+fn sync_report(file: &LocalFile, conflicts: Vec<SyncConflict>) -> SyncReport {
+    SyncReport {
+        backend: file.sync_backend().to_string(),
+        conflicts: conflicts
+            .iter()
+            .map(|conflict| ConflictSummary {
+                task_id: conflict.task_id,
+                label: conflict
+                    .local
+                    .as_ref()
+                    .or(conflict.remote.as_ref())
+                    .map(MyTask::label)
+                    .unwrap_or_else(|| format!("Task {}", conflict.task_id)),
+                kept: if conflict.local.is_some() { "local" } else { "remote" }.to_string(),
+            })
+            .collect(),
+    }
+}
+
+fn sync_text(done: &str, report: &SyncReport) -> String {
+    let mut lines = vec![format!("✅ {} {}", done, report.backend)];
+
+    for conflict in &report.conflicts {
+        lines.push(format!(
+            "⚠️ \"{}\" was changed on both sides, the {} version was kept",
+            conflict.label,
+            if conflict.kept == "local" { "local" } else { "synced" }
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn habit(start: NaiveDate, end: NaiveDate) -> CreateArgs {
+        CreateArgs {
+            action: Some("Read".to_string()),
+            count_per_day: Some(10.0),
+            unit: Some("pages".to_string()),
+            end: Some(end),
+            name: None,
+            daily_tasks: None,
+            start: Some(start),
+            days: vec![Weekday::Fri, Weekday::Mon, Weekday::Mon],
+        }
+    }
+
+    fn goal(daily_tasks: PathBuf) -> CreateArgs {
+        CreateArgs {
+            action: None,
+            count_per_day: None,
+            unit: None,
+            end: None,
+            name: Some("Learn Rust".to_string()),
+            daily_tasks: Some(daily_tasks),
+            start: Some(date(2026, 3, 2)),
+            days: vec![],
+        }
+    }
+
+    #[test]
+    fn a_habit_asks_for_its_count_every_effective_day() {
+        let task = new_task(habit(date(2026, 3, 2), date(2026, 3, 31)), date(2026, 3, 1)).unwrap();
+
+        assert_eq!(task.label(), "Read 10 pages");
+        assert_eq!(task.end, date(2026, 3, 31));
+        assert_eq!(task.effective_dow, vec![Weekday::Mon, Weekday::Fri]);
+        assert_eq!(task.daily_tasks, None);
+    }
+
+    #[test]
+    fn a_habit_ending_before_it_starts_is_refused() {
+        assert!(new_task(habit(date(2026, 3, 2), date(2026, 3, 1)), date(2026, 3, 1)).is_err());
+    }
+
+    #[test]
+    fn a_goal_takes_one_effective_day_per_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plan.txt");
+        std::fs::write(&path, "Install\n\n  Read the book  \nWrite a CLI\n").unwrap();
+
+        let task = new_task(goal(path), date(2026, 3, 1)).unwrap();
+
+        assert_eq!(task.label(), "Learn Rust");
+        assert_eq!(
+            task.daily_tasks,
+            Some(vec![
+                "Install".to_string(),
+                "Read the book".to_string(),
+                "Write a CLI".to_string()
+            ])
+        );
+        assert_eq!(task.end, date(2026, 3, 4));
+    }
+
+    #[test]
+    fn a_goal_without_daily_activities_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plan.txt");
+        std::fs::write(&path, "\n  \n").unwrap();
+
+        let error = new_task(goal(path), date(2026, 3, 1)).unwrap_err();
+
+        assert!(error.contains("has no daily activities"), "{}", error);
+    }
+
+    #[test]
+    fn logging_needs_a_positive_amount_on_an_effective_day_up_to_today() {
+        let today = date(2026, 3, 11);
+        let task = new_task(habit(date(2026, 3, 2), date(2026, 3, 31)), today).unwrap();
+
+        // Monday and Friday are effective
+        assert_eq!(check_log(&task, 5.0, date(2026, 3, 9), today), Ok(()));
+        assert!(check_log(&task, 0.0, date(2026, 3, 9), today).is_err());
+        assert!(check_log(&task, -1.0, date(2026, 3, 9), today).is_err());
+        assert!(check_log(&task, 5.0, date(2026, 3, 10), today).is_err());
+        assert!(check_log(&task, 5.0, date(2026, 3, 13), today).is_err());
+        assert!(check_log(&task, 5.0, date(2026, 2, 27), today).is_err());
+    }
+
+    #[test]
+    fn sync_reports_name_the_version_kept() {
+        let task = new_task(habit(date(2026, 3, 2), date(2026, 3, 31)), date(2026, 3, 1)).unwrap();
+        let report = SyncReport {
+            backend: "WebDAV".to_string(),
+            conflicts: vec![ConflictSummary {
+                task_id: task.id,
+                label: task.label(),
+                kept: "remote".to_string(),
+            }],
+        };
+
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "backend": "WebDAV",
+                "conflicts": [{ "task_id": task.id, "label": "Read 10 pages", "kept": "remote" }]
+            })
+        );
+    }
+}
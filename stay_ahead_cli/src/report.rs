@@ -0,0 +1,376 @@
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+use stay_ahead_core::schedule::{
+    fill_ratio_parallel_universe, fill_ratio_user_universe, generate_date_range, task_forecast,
+    task_lead, Projection,
+};
+use stay_ahead_core::serializable_log_entry::SerializableLogEntry;
+use stay_ahead_core::streak::task_streaks;
+use stay_ahead_core::MyTask;
+
+const BAR_WIDTH: usize = 10;
+
+/*
+ * Struct Definition
+ */
+
+// What `list` prints for each task, and what `show` prints above the timeline
+#[derive(Debug, Serialize)]
+pub struct TaskSummary {
+    pub id: i64,
+    pub label: String,
+    pub action: String,
+    pub count_per_day: f32,
+    pub unit: String,
+    pub start: String,
+    pub end: String,
+    pub effective_dow: Vec<String>,
+    pub archived: bool,
+    pub accomplished: f32,
+    pub planned_to_date: f32,
+    pub total: f32,
+    pub lead_units: f32,
+    pub lead_days: f32,
+    pub lead: String,
+    pub streak: usize,
+    pub longest_streak: usize,
+    pub ahead_streak: usize,
+    pub longest_ahead_streak: usize,
+    pub streaks: String,
+}
+
+// One effective day of the timeline, the fills being between 0 and 1
+#[derive(Debug, Serialize)]
+pub struct TimelineDay {
+    pub date: String,
+    pub weekday: String,
+    pub parallel_universe: f32,
+    pub your_universe: f32,
+    pub logged: f32,
+    pub daily_task: Option<String>,
+}
+
+// `projected_finish` is a date, "done", or `None` without any pace yet
+#[derive(Debug, Serialize)]
+pub struct ForecastSummary {
+    pub pace: f32,
+    pub projected_finish: Option<String>,
+    pub required_pace: Option<f32>,
+    pub days_left: usize,
+    pub slipping: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskTimeline {
+    pub task: TaskSummary,
+    pub forecast: ForecastSummary,
+    pub days: Vec<TimelineDay>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoggedEntry {
+    pub task_id: i64,
+    pub entry: SerializableLogEntry,
+    pub accomplished: f32,
+}
+
+/*
+ * Building the reports
+ */
+
+impl TaskSummary {
+    pub fn new(task: &MyTask, today: NaiveDate) -> Self {
+        let dates = generate_date_range(task.start, task.end, task.effective_dow.clone());
+        let days_passed_inclusive = dates.iter().filter(|&&date| date <= today).count();
+        let lead = task_lead(task, today);
+        let streaks = task_streaks(task, today);
+
+        TaskSummary {
+            id: task.id,
+            label: task.label(),
+            action: task.action.clone(),
+            count_per_day: task.count_per_day,
+            unit: task.unit.clone(),
+            start: task.start.to_string(),
+            end: task.end.to_string(),
+            effective_dow: task.effective_dow.iter().map(|d| d.to_string()).collect(),
+            archived: task.archive,
+            accomplished: task.count_accum(),
+            planned_to_date: days_passed_inclusive as f32 * task.count_per_day,
+            total: dates.len() as f32 * task.count_per_day,
+            lead_units: lead.units,
+            lead_days: lead.days,
+            lead: lead.describe(&task.unit),
+            streak: streaks.daily.current,
+            longest_streak: streaks.daily.longest,
+            ahead_streak: streaks.ahead.current,
+            longest_ahead_streak: streaks.ahead.longest,
+            streaks: streaks.describe(),
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        format!(
+            "[{}] {}{}\n    {:.1} of {:.1} {} done, {:.1} planned by today ({} to {})\n    {}\n    {}",
+            self.id,
+            self.label,
+            if self.archived { " (archived)" } else { "" },
+            self.accomplished,
+            self.total,
+            self.unit,
+            self.planned_to_date,
+            self.start,
+            self.end,
+            self.lead,
+            self.streaks
+        )
+    }
+}
+
+impl TaskTimeline {
+    // Past days are left out with `from_today`, like "Skip to today" in the app
+    pub fn new(task: &MyTask, today: NaiveDate, from_today: bool, pace_warning_multiple: f32) -> Self {
+        let dates = generate_date_range(task.start, task.end, task.effective_dow.clone());
+        let user_accomplished = task.count_accum();
+        let logged_per_day = task.logged_per_day();
+
+        let days = dates
+            .iter()
+            .enumerate()
+            .filter(|(_, &date)| !from_today || date >= today)
+            .map(|(i, &date)| TimelineDay {
+                date: date.to_string(),
+                weekday: date.weekday().to_string(),
+                parallel_universe: fill_ratio_parallel_universe(date, today),
+                your_universe: fill_ratio_user_universe(i, task.count_per_day, user_accomplished),
+                logged: logged_per_day.get(&date).copied().unwrap_or(0.0),
+                daily_task: task
+                    .daily_tasks
+                    .as_ref()
+                    .and_then(|daily_tasks| daily_tasks.get(i).cloned()),
+            })
+            .collect();
+
+        let forecast = task_forecast(task, today);
+
+        TaskTimeline {
+            task: TaskSummary::new(task, today),
+            forecast: ForecastSummary {
+                pace: forecast.pace,
+                projected_finish: match forecast.projected_finish {
+                    Projection::Done => Some("done".to_string()),
+                    Projection::On(date) => Some(date.to_string()),
+                    Projection::Never => None,
+                },
+                required_pace: forecast.required_pace,
+                days_left: forecast.days_left,
+                slipping: forecast.is_slipping(task.count_per_day, pace_warning_multiple),
+            },
+            days,
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let task = &self.task;
+        let mut lines = vec![
+            task.to_text(),
+            String::new(),
+            format!(
+                "Parallel Universe: {:.1} accomplished, {:.1} remaining",
+                task.planned_to_date,
+                task.total - task.planned_to_date
+            ),
+            format!(
+                "Your Universe:     {:.1} accomplished, {:.1} remaining",
+                task.accomplished,
+                task.total - task.accomplished
+            ),
+            String::new(),
+            self.forecast_text(),
+            String::new(),
+            format!("{:<12}  {:<16}  {:<12}", "Parallel", "Timeline", "Yours"),
+        ];
+
+        for day in &self.days {
+            let mut line = format!(
+                "{}  {} [{}]  {}",
+                bar(day.parallel_universe),
+                day.date,
+                day.weekday,
+                bar(day.your_universe)
+            );
+
+            if day.logged > 0.0 {
+                line.push_str(&format!("  +{:.1} logged", day.logged));
+            }
+            if let Some(daily_task) = &day.daily_task {
+                line.push_str(&format!("  {}", daily_task));
+            }
+
+            lines.push(line);
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl TaskTimeline {
+    fn forecast_text(&self) -> String {
+        let task = &self.task;
+        let forecast = &self.forecast;
+
+        let finish = match forecast.projected_finish.as_deref() {
+            Some("done") => return "All done!".to_string(),
+            Some(date) => format!(
+                "At your pace of {:.1} {} per effective day, you will finish on {}",
+                forecast.pace, task.unit, date
+            ),
+            None => "Log some progress to see when you will finish".to_string(),
+        };
+
+        let required = match forecast.required_pace {
+            Some(required_pace) => format!(
+                "To finish by {} you now need {:.1} {} per effective day ({} day(s) left)",
+                task.end, required_pace, task.unit, forecast.days_left
+            ),
+            None => format!("The deadline {} has passed", task.end),
+        };
+
+        let mut lines = vec![finish, required];
+        if forecast.slipping && forecast.required_pace.is_some() {
+            lines.push("⚠️ This goal is slipping".to_string());
+        }
+
+        lines.join("\n")
+    }
+}
+
+fn bar(ratio: f32) -> String {
+    let filled = ((ratio.clamp(0.0, 1.0) * BAR_WIDTH as f32).round()) as usize;
+    format!("[{}{}]", "#".repeat(filled), ".".repeat(BAR_WIDTH - filled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use stay_ahead_core::log_entry::LogEntry;
+    use stay_ahead_core::schedule::ALL_WEEKDAYS;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn task() -> MyTask {
+        MyTask {
+            id: 1,
+            action: "Read".to_string(),
+            count_per_day: 10.0,
+            unit: "pages".to_string(),
+            logs: vec![LogEntry::new(date(1), 25.0, None)],
+            start: date(1),
+            end: date(10),
+            effective_dow: ALL_WEEKDAYS.to_vec(),
+            daily_tasks: None,
+            name: None,
+            archive: false,
+        }
+    }
+
+    fn keys(value: &Value) -> Vec<&str> {
+        let mut keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    // Scripts read these fields, so renaming one is a breaking change
+    #[test]
+    fn json_output_keeps_its_shape() {
+        let timeline =
+            serde_json::to_value(TaskTimeline::new(&task(), date(3), false, 1.5)).unwrap();
+
+        assert_eq!(keys(&timeline), vec!["days", "forecast", "task"]);
+        assert_eq!(
+            keys(&timeline["task"]),
+            vec![
+                "accomplished",
+                "action",
+                "ahead_streak",
+                "archived",
+                "count_per_day",
+                "effective_dow",
+                "end",
+                "id",
+                "label",
+                "lead",
+                "lead_days",
+                "lead_units",
+                "longest_ahead_streak",
+                "longest_streak",
+                "planned_to_date",
+                "start",
+                "streak",
+                "streaks",
+                "total",
+                "unit",
+            ]
+        );
+        assert_eq!(
+            keys(&timeline["forecast"]),
+            vec![
+                "days_left",
+                "pace",
+                "projected_finish",
+                "required_pace",
+                "slipping"
+            ]
+        );
+        assert_eq!(timeline["days"].as_array().unwrap().len(), 10);
+        assert_eq!(
+            keys(&timeline["days"][0]),
+            vec![
+                "daily_task",
+                "date",
+                "logged",
+                "parallel_universe",
+                "weekday",
+                "your_universe"
+            ]
+        );
+        assert_eq!(timeline["days"][0]["date"], "2026-03-01");
+        assert_eq!(timeline["days"][0]["logged"], 25.0);
+
+        let entry = LogEntry::new(date(3), 5.0, Some("Before bed".to_string()));
+        let logged = serde_json::to_value(LoggedEntry {
+            task_id: 1,
+            entry: SerializableLogEntry::from(&entry),
+            accomplished: 30.0,
+        })
+        .unwrap();
+
+        assert_eq!(keys(&logged), vec!["accomplished", "entry", "task_id"]);
+        assert_eq!(
+            keys(&logged["entry"]),
+            vec!["amount", "id", "note", "timestamp"]
+        );
+        assert_eq!(logged["entry"]["note"], "Before bed");
+    }
+
+    #[test]
+    fn amounts_logged_on_a_day_are_rounded_in_the_timeline() {
+        let mut task = task();
+        task.logs = vec![
+            LogEntry::new(date(2), 0.1, None),
+            LogEntry::new(date(2), 0.2, None),
+        ];
+
+        let text = TaskTimeline::new(&task, date(3), false, 1.5).to_text();
+
+        assert!(text.contains("  +0.3 logged"), "{}", text);
+    }
+}
@@ -0,0 +1,174 @@
+use super::log_entry::LogEntry;
+use super::MyTask;
+use chrono::{Datelike, NaiveDate};
+
+/*
+ * Struct Definition
+ */
+
+// One day across tasks. Tasks do not share a unit, so each due task counts as
+// one day's worth of work, done as far as its `count_per_day` was logged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DayActivity {
+    pub date: NaiveDate,
+    // Tasks with this date as one of their effective days
+    pub n_planned: usize,
+    // Between 0 and `n_planned`, logging more than a day's amount does not count twice
+    pub done: f32,
+}
+
+impl DayActivity {
+    // `None` when nothing was planned for the day
+    pub fn fraction(&self) -> Option<f32> {
+        if self.n_planned == 0 {
+            None
+        } else {
+            Some(self.done / self.n_planned as f32)
+        }
+    }
+}
+
+/*
+ * Activity day by day, from `from` to `to` inclusive
+ */
+
+pub fn activity_by_day<'a>(
+    tasks: impl IntoIterator<Item = &'a MyTask>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<DayActivity> {
+    let mut days: Vec<DayActivity> = from
+        .iter_days()
+        .take_while(|date| *date <= to)
+        .map(|date| DayActivity {
+            date,
+            n_planned: 0,
+            done: 0.0,
+        })
+        .collect();
+
+    for task in tasks {
+        let logged_per_day = task.logged_per_day();
+
+        for day in days.iter_mut().filter(|day| is_effective_day(task, day.date)) {
+            let logged = logged_per_day.get(&day.date).copied().unwrap_or(0.0);
+
+            day.n_planned += 1;
+            if task.count_per_day > 0.0 {
+                day.done += (logged / task.count_per_day).clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    days
+}
+
+// What was logged on a day for each task that has something, in the order it was submitted
+pub fn entries_on<'a>(
+    tasks: impl IntoIterator<Item = &'a MyTask>,
+    date: NaiveDate,
+) -> Vec<(&'a MyTask, Vec<&'a LogEntry>)> {
+    let mut entries = vec![];
+
+    for task in tasks {
+        let mut logged: Vec<&LogEntry> = task
+            .logs
+            .iter()
+            .filter(|entry| entry.timestamp.date() == date)
+            .collect();
+
+        if !logged.is_empty() {
+            logged.sort_by_key(|entry| entry.timestamp);
+            entries.push((task, logged));
+        }
+    }
+
+    entries
+}
+
+fn is_effective_day(task: &MyTask, date: NaiveDate) -> bool {
+    task.start <= date && date <= task.end && task.effective_dow.contains(&date.weekday())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::ALL_WEEKDAYS;
+    use chrono::Weekday;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    fn task(id: i64, count_per_day: f32, effective_dow: Vec<Weekday>) -> MyTask {
+        MyTask {
+            id,
+            action: "Read".to_string(),
+            count_per_day,
+            unit: "pages".to_string(),
+            logs: vec![],
+            start: date(2),
+            end: date(8),
+            effective_dow,
+            daily_tasks: None,
+            name: None,
+            archive: false,
+        }
+    }
+
+    #[test]
+    fn each_due_task_counts_as_one_day_of_work() {
+        let mut reading = task(1, 10.0, ALL_WEEKDAYS.to_vec());
+        reading.logs.push(LogEntry::new(date(2), 5.0, None));
+        // Logging more than a day's amount does not make up for another task
+        reading.logs.push(LogEntry::new(date(3), 50.0, None));
+
+        let mut running = task(2, 3.0, vec![Weekday::Mon]);
+        running.logs.push(LogEntry::new(date(2), 3.0, None));
+
+        let days = activity_by_day([&reading, &running], date(1), date(3));
+
+        assert_eq!(
+            days,
+            vec![
+                DayActivity {
+                    date: date(1),
+                    n_planned: 0,
+                    done: 0.0
+                },
+                DayActivity {
+                    date: date(2),
+                    n_planned: 2,
+                    done: 1.5
+                },
+                DayActivity {
+                    date: date(3),
+                    n_planned: 1,
+                    done: 1.0
+                },
+            ]
+        );
+        assert_eq!(days[0].fraction(), None);
+        assert_eq!(days[1].fraction(), Some(0.75));
+    }
+
+    #[test]
+    fn entries_on_a_day_are_listed_per_task_in_submission_order() {
+        let mut reading = task(1, 10.0, ALL_WEEKDAYS.to_vec());
+        let later = LogEntry::new(date(2), 2.0, None);
+        let mut earlier = LogEntry::new(date(2), 1.0, None);
+        earlier.timestamp -= chrono::Duration::hours(1);
+        reading.logs = vec![
+            later.clone(),
+            earlier.clone(),
+            LogEntry::new(date(3), 4.0, None),
+        ];
+        let running = task(2, 3.0, ALL_WEEKDAYS.to_vec());
+
+        let entries = entries_on([&reading, &running], date(2));
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0.id, 1);
+        assert_eq!(entries[0].1, vec![&earlier, &later]);
+    }
+}
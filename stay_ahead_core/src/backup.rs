@@ -0,0 +1,65 @@
+use chrono::NaiveDateTime;
+
+// Backups are named after when they were taken, so they sort oldest first
+pub const BACKUP_ID_FORMAT: &str = "%Y%m%d%H%M%S";
+
+// At most one backup per interval, so typing in a form does not flush out older ones
+pub const BACKUP_INTERVAL_MINUTES: i64 = 60;
+
+/*
+ * When to take a rolling backup and which ones to drop
+ *
+ * `ids` are the backups there are, oldest first. Where they are stored is up
+ * to the platform.
+ */
+
+pub fn backup_due(ids: &[String], now: NaiveDateTime) -> bool {
+    ids.last()
+        .and_then(|id| NaiveDateTime::parse_from_str(id, BACKUP_ID_FORMAT).ok())
+        .is_none_or(|created| now - created >= chrono::Duration::minutes(BACKUP_INTERVAL_MINUTES))
+}
+
+// The oldest backups, leaving the newest `keep`
+pub fn backups_to_prune(ids: &[String], keep: usize) -> &[String] {
+    &ids[..ids.len().saturating_sub(keep)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 3, 1)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn id(hour: u32, minute: u32) -> String {
+        at(hour, minute).format(BACKUP_ID_FORMAT).to_string()
+    }
+
+    #[test]
+    fn a_backup_is_due_an_interval_after_the_newest() {
+        let ids = vec![id(8, 0), id(10, 30)];
+
+        assert!(!backup_due(&ids, at(11, 29)));
+        assert!(backup_due(&ids, at(11, 30)));
+    }
+
+    #[test]
+    fn a_backup_is_due_without_any_readable_one() {
+        assert!(backup_due(&[], at(10, 0)));
+        assert!(backup_due(&["copied by hand".to_string()], at(10, 0)));
+    }
+
+    #[test]
+    fn the_oldest_backups_are_pruned() {
+        let ids = vec![id(8, 0), id(9, 0), id(10, 0)];
+
+        assert_eq!(backups_to_prune(&ids, 2), &ids[..1]);
+        assert_eq!(backups_to_prune(&ids, 0), &ids[..]);
+        assert!(backups_to_prune(&ids, 5).is_empty());
+    }
+}
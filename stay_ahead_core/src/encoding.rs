@@ -0,0 +1,146 @@
+use base64::{engine::general_purpose, Engine as _};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+// Marks a value encrypted with the device key, anything else is the old XOR encoding
+const ENCRYPTED_PREFIX: &str = "enc1:";
+
+const NONCE_LEN: usize = 12;
+
+pub const KEY_LEN: usize = 32;
+
+pub fn generate_key() -> Result<[u8; KEY_LEN], String> {
+    let mut key = [0u8; KEY_LEN];
+    getrandom::getrandom(&mut key).map_err(|e| format!("Failed to generate a key: {}", e))?;
+    Ok(key)
+}
+
+pub fn encrypt(input: &str, key: &[u8; KEY_LEN]) -> Result<String, String> {
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::getrandom(&mut nonce).map_err(|e| format!("Failed to generate a nonce: {}", e))?;
+
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), input.as_bytes())
+        .map_err(|_| "Encryption failed".to_string())?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend(ciphertext);
+
+    Ok(format!(
+        "{}{}",
+        ENCRYPTED_PREFIX,
+        general_purpose::STANDARD.encode(payload)
+    ))
+}
+
+// Values written by older versions are still accepted, so they get encrypted on the next save
+pub fn decrypt(input: &str, key: &[u8; KEY_LEN]) -> Option<String> {
+    let Some(encoded) = input.strip_prefix(ENCRYPTED_PREFIX) else {
+        return decode_legacy(input);
+    };
+
+    let payload = general_purpose::STANDARD.decode(encoded).ok()?;
+    if payload.len() < NONCE_LEN {
+        return None;
+    }
+
+    let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+    let plaintext = cipher.decrypt(Nonce::from_slice(nonce), ciphertext).ok()?;
+
+    String::from_utf8(plaintext).ok()
+}
+
+/*
+ * XOR obfuscation used before secrets were encrypted, only kept for migration
+ */
+
+const LEGACY_KEY: &str = "OBFUSCATION_ONLY";
+
+fn decode_legacy(input: &str) -> Option<String> {
+    let decoded = decode_hex(input)?;
+
+    Some(
+        decoded
+            .iter()
+            .zip(LEGACY_KEY.as_bytes().iter().cycle())
+            .map(|(a, b)| a ^ b)
+            .map(|b| b as char)
+            .collect(),
+    )
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let hi = (pair[0] as char).to_digit(16)?;
+            let lo = (pair[1] as char).to_digit(16)?;
+            Some((hi * 16 + lo) as u8)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    // What versions before the device key wrote
+    fn encode_legacy(input: &str) -> String {
+        input
+            .as_bytes()
+            .iter()
+            .zip(LEGACY_KEY.as_bytes().iter().cycle())
+            .map(|(a, b)| format!("{:02x}", a ^ b))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn decode_hex_never_panics(input in any::<String>()) {
+            let _ = decode_hex(&input);
+        }
+
+        #[test]
+        fn decode_hex_reads_any_hex(bytes in vec(any::<u8>(), 0..64)) {
+            let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+            prop_assert_eq!(decode_hex(&hex), Some(bytes));
+        }
+
+        #[test]
+        fn decrypt_never_panics(input in any::<String>(), key in any::<[u8; KEY_LEN]>()) {
+            let _ = decrypt(&input, &key);
+        }
+
+        #[test]
+        fn decrypt_rejects_tampered_payloads(payload in vec(any::<u8>(), 0..64), key in any::<[u8; KEY_LEN]>()) {
+            let input = format!("{}{}", ENCRYPTED_PREFIX, general_purpose::STANDARD.encode(payload));
+            prop_assert_eq!(decrypt(&input, &key), None);
+        }
+
+        #[test]
+        fn encrypted_secrets_round_trip(secret in any::<String>(), key in any::<[u8; KEY_LEN]>()) {
+            let encrypted = encrypt(&secret, &key).unwrap();
+            prop_assert_eq!(decrypt(&encrypted, &key), Some(secret));
+        }
+
+        #[test]
+        fn legacy_secrets_are_still_read(secret in "[ -~]{0,40}") {
+            prop_assert_eq!(decrypt(&encode_legacy(&secret), &[0u8; KEY_LEN]), Some(secret));
+        }
+    }
+
+    #[test]
+    fn another_device_key_does_not_decrypt() {
+        let encrypted = encrypt("ghp_secret", &[1u8; KEY_LEN]).unwrap();
+        assert_eq!(decrypt(&encrypted, &[2u8; KEY_LEN]), None);
+    }
+}
@@ -0,0 +1,254 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose, Engine as _};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+
+const ENVELOPE_VERSION: u32 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+// The parameters come with the payload, so anyone able to write to the sync target could
+// otherwise make every device that opens it allocate gigabytes or hash for hours.
+// Far above the defaults, to leave room for raising them later.
+const MAX_MEMORY_KIB: u32 = 256 * 1024;
+const MAX_ITERATIONS: u32 = 64;
+const MAX_PARALLELISM: u32 = 16;
+
+/*
+ * Struct Definition
+ *
+ * What gets pushed when a sync passphrase is set. The KDF parameters travel
+ * with the payload, so they can be raised later without breaking older data.
+ */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Envelope {
+    stay_ahead_envelope: u32,
+    kdf: KdfParams,
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KdfParams {
+    algorithm: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    salt: String,
+}
+
+// Only used to tell an envelope apart from a plain state
+#[derive(Deserialize)]
+struct EnvelopeHeader {
+    stay_ahead_envelope: u32,
+}
+
+/*
+ * Sealing and opening of sync payloads
+ */
+
+pub fn seal(plaintext: &str, passphrase: &str) -> Result<String, String> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::getrandom(&mut salt).map_err(|e| format!("❌ Failed to generate a salt: {}", e))?;
+    getrandom::getrandom(&mut nonce)
+        .map_err(|e| format!("❌ Failed to generate a nonce: {}", e))?;
+
+    let kdf = KdfParams {
+        algorithm: "argon2id".to_string(),
+        memory_kib: Params::DEFAULT_M_COST,
+        iterations: Params::DEFAULT_T_COST,
+        parallelism: Params::DEFAULT_P_COST,
+        salt: general_purpose::STANDARD.encode(salt),
+    };
+
+    let key = derive_key(passphrase, &kdf, &salt)?;
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key))
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+        .map_err(|_| "❌ Failed to encrypt the sync payload".to_string())?;
+
+    let envelope = Envelope {
+        stay_ahead_envelope: ENVELOPE_VERSION,
+        kdf,
+        nonce: general_purpose::STANDARD.encode(nonce),
+        ciphertext: general_purpose::STANDARD.encode(ciphertext),
+    };
+
+    serde_json::to_string_pretty(&envelope).map_err(|e| format!("❌ Serialization failed: {}", e))
+}
+
+pub fn is_sealed(content: &str) -> bool {
+    serde_json::from_str::<EnvelopeHeader>(content).is_ok()
+}
+
+// Plain payloads are passed through, so unencrypted sync targets keep working
+pub fn open(content: &str, passphrase: Option<&str>) -> Result<String, String> {
+    let Ok(header) = serde_json::from_str::<EnvelopeHeader>(content) else {
+        return Ok(content.to_string());
+    };
+
+    if header.stay_ahead_envelope > ENVELOPE_VERSION {
+        return Err(format!(
+            "❌ The synced data is encrypted with a newer format (version {}). Please update the app.",
+            header.stay_ahead_envelope
+        ));
+    }
+
+    if header.stay_ahead_envelope != ENVELOPE_VERSION {
+        return Err(format!(
+            "❌ The synced data is encrypted with an unknown format (version {})",
+            header.stay_ahead_envelope
+        ));
+    }
+
+    let Some(passphrase) = passphrase.filter(|p| !p.is_empty()) else {
+        return Err(
+            "🔒 The synced data is end-to-end encrypted. Enter its passphrase in the sync settings."
+                .to_string(),
+        );
+    };
+
+    let envelope: Envelope = serde_json::from_str(content)
+        .map_err(|e| format!("❌ Failed to read the encrypted payload: {}", e))?;
+
+    let decode = |value: &str| {
+        general_purpose::STANDARD
+            .decode(value)
+            .map_err(|e| format!("❌ Failed to read the encrypted payload: {}", e))
+    };
+
+    let salt = decode(&envelope.kdf.salt)?;
+    let nonce = decode(&envelope.nonce)?;
+    let ciphertext = decode(&envelope.ciphertext)?;
+
+    if nonce.len() != NONCE_LEN {
+        return Err("❌ Failed to read the encrypted payload: invalid nonce".to_string());
+    }
+
+    let key = derive_key(passphrase, &envelope.kdf, &salt)?;
+    let plaintext = ChaCha20Poly1305::new(Key::from_slice(&key))
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| "❌ Wrong sync passphrase, the synced data cannot be decrypted".to_string())?;
+
+    String::from_utf8(plaintext)
+        .map_err(|e| format!("❌ Failed to read the encrypted payload: {}", e))
+}
+
+fn derive_key(passphrase: &str, kdf: &KdfParams, salt: &[u8]) -> Result<[u8; KEY_LEN], String> {
+    if kdf.algorithm != "argon2id" {
+        return Err(format!("❌ Unsupported key derivation: {}", kdf.algorithm));
+    }
+
+    if kdf.memory_kib > MAX_MEMORY_KIB
+        || kdf.iterations > MAX_ITERATIONS
+        || kdf.parallelism > MAX_PARALLELISM
+    {
+        return Err(format!(
+            "❌ The key derivation parameters are too costly to open ({} KiB, {} iterations, {} lanes)",
+            kdf.memory_kib, kdf.iterations, kdf.parallelism
+        ));
+    }
+
+    let params = Params::new(
+        kdf.memory_kib,
+        kdf.iterations,
+        kdf.parallelism,
+        Some(KEY_LEN),
+    )
+    .map_err(|e| format!("❌ Invalid key derivation parameters: {}", e))?;
+
+    let mut key = [0u8; KEY_LEN];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("❌ Key derivation failed: {}", e))?;
+
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATE: &str = r#"{"schema_version":1,"tasks":[]}"#;
+
+    #[test]
+    fn a_sealed_payload_opens_with_its_passphrase() {
+        let sealed = seal(STATE, "correct horse").unwrap();
+
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.contains("schema_version"));
+        assert_eq!(open(&sealed, Some("correct horse")).unwrap(), STATE);
+    }
+
+    #[test]
+    fn a_wrong_or_missing_passphrase_is_rejected() {
+        let sealed = seal(STATE, "correct horse").unwrap();
+
+        assert!(open(&sealed, Some("battery staple"))
+            .unwrap_err()
+            .starts_with("❌ Wrong sync passphrase"));
+        assert!(open(&sealed, None).unwrap_err().starts_with("🔒"));
+        assert!(open(&sealed, Some("")).unwrap_err().starts_with("🔒"));
+    }
+
+    #[test]
+    fn a_plain_payload_is_passed_through() {
+        assert!(!is_sealed(STATE));
+        assert_eq!(open(STATE, None).unwrap(), STATE);
+        assert_eq!(open(STATE, Some("correct horse")).unwrap(), STATE);
+    }
+
+    fn with_envelope(sealed: &str, change: impl FnOnce(&mut Envelope)) -> String {
+        let mut envelope: Envelope = serde_json::from_str(sealed).unwrap();
+        change(&mut envelope);
+        serde_json::to_string(&envelope).unwrap()
+    }
+
+    #[test]
+    fn too_costly_key_derivation_is_refused_before_deriving() {
+        let sealed = seal(STATE, "correct horse").unwrap();
+
+        let costly = [
+            with_envelope(&sealed, |e| e.kdf.memory_kib = u32::MAX),
+            with_envelope(&sealed, |e| e.kdf.iterations = u32::MAX),
+            with_envelope(&sealed, |e| e.kdf.parallelism = MAX_PARALLELISM + 1),
+        ];
+
+        for content in costly {
+            assert!(open(&content, Some("correct horse"))
+                .unwrap_err()
+                .contains("too costly"));
+        }
+    }
+
+    #[test]
+    fn only_a_higher_version_is_called_newer() {
+        let sealed = seal(STATE, "correct horse").unwrap();
+
+        let newer = with_envelope(&sealed, |e| e.stay_ahead_envelope = ENVELOPE_VERSION + 1);
+        assert!(open(&newer, None).unwrap_err().contains("newer format"));
+
+        let unknown = with_envelope(&sealed, |e| e.stay_ahead_envelope = 0);
+        let error = open(&unknown, None).unwrap_err();
+        assert!(error.contains("unknown format"), "{}", error);
+    }
+
+    #[test]
+    fn a_tampered_ciphertext_is_rejected() {
+        let sealed = seal(STATE, "correct horse").unwrap();
+        let mut envelope: Envelope = serde_json::from_str(&sealed).unwrap();
+        let mut ciphertext = general_purpose::STANDARD
+            .decode(&envelope.ciphertext)
+            .unwrap();
+        ciphertext[0] ^= 1;
+        envelope.ciphertext = general_purpose::STANDARD.encode(ciphertext);
+
+        let tampered = serde_json::to_string(&envelope).unwrap();
+
+        assert!(open(&tampered, Some("correct horse")).is_err());
+    }
+}
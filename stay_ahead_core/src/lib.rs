@@ -0,0 +1,31 @@
+/*
+ * Everything Stay Ahead does that does not need a UI: the task model,
+ * scheduling math, saved data with its migrations, and the sync protocol
+ */
+
+pub mod activity;
+pub mod backup;
+pub mod encoding;
+pub mod envelope;
+pub mod load_error;
+pub mod log_entry;
+pub mod merge;
+pub mod migration;
+pub mod outbox;
+pub mod pairing;
+pub mod schedule;
+pub mod serializable_log_entry;
+pub mod serializable_state;
+pub mod serializable_task;
+pub mod state_file;
+pub mod streak;
+pub mod sync;
+pub mod sync_backend;
+pub mod task;
+
+pub use load_error::{LoadError, QuarantinedTask, TaskError};
+pub use log_entry::LogEntry;
+pub use merge::{SyncBase, SyncConflict};
+pub use pairing::Pairing;
+pub use serializable_state::SerializableState;
+pub use task::MyTask;
@@ -0,0 +1,38 @@
+use super::migration::MigrationError;
+use serde::{Deserialize, Serialize};
+
+/*
+ * Errors while loading the saved state
+ */
+
+#[derive(thiserror::Error, Debug)]
+pub enum LoadError {
+    #[error("Failed to read state file: {0}")]
+    FileNotFound(#[from] std::io::Error),
+
+    #[error("Failed to parse JSON state: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Migration(#[from] MigrationError),
+}
+
+/*
+ * Errors while converting a single task
+ */
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum TaskError {
+    #[error("Invalid date: {0}")]
+    InvalidDate(#[from] chrono::ParseError),
+
+    #[error("Invalid day of week: {0}")]
+    InvalidWeekday(String),
+}
+
+// A task that could not be read. It is kept aside as it was found instead of being dropped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuarantinedTask {
+    pub raw: serde_json::Value,
+    pub error: String,
+}
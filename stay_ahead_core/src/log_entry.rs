@@ -0,0 +1,93 @@
+use super::serializable_log_entry::SerializableLogEntry;
+use chrono::{Local, NaiveDate, NaiveDateTime, Timelike};
+
+pub const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+// Low bits of an ID that are random, below the milliseconds it was created at
+const RANDOM_ID_BITS: u32 = 20;
+
+/*
+ * Struct Definition
+ */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub id: i64,
+    pub amount: f32,
+    pub timestamp: NaiveDateTime,
+    pub note: Option<String>,
+}
+
+impl LogEntry {
+    // Backdated entries keep the current time of day so that they still sort by submission.
+    // Only whole seconds are saved, so anything finer would make the entry differ from its synced copy.
+    pub fn new(date: NaiveDate, amount: f32, note: Option<String>) -> Self {
+        let now = Local::now().time();
+
+        Self {
+            id: new_id(),
+            amount,
+            timestamp: date.and_time(now.with_nanosecond(0).unwrap_or(now)),
+            note,
+        }
+    }
+}
+
+// Entries logged in the same millisecond on two devices would otherwise share an ID and
+// conflict when synced. IDs still grow with time, so the last one created has the largest,
+// also compared with the IDs from before, which were only the milliseconds.
+fn new_id() -> i64 {
+    let mut random = [0u8; 4];
+    let random = match getrandom::getrandom(&mut random) {
+        Ok(()) => u32::from_le_bytes(random),
+        Err(_) => chrono::Utc::now().timestamp_subsec_nanos(),
+    };
+
+    (chrono::Utc::now().timestamp_millis() << RANDOM_ID_BITS)
+        | (random & ((1 << RANDOM_ID_BITS) - 1)) as i64
+}
+
+/*
+ * Conversion from SerializableLogEntry to LogEntry
+ */
+
+impl TryFrom<&SerializableLogEntry> for LogEntry {
+    type Error = chrono::ParseError;
+
+    fn try_from(entry: &SerializableLogEntry) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: entry.id,
+            amount: entry.amount,
+            timestamp: NaiveDateTime::parse_from_str(&entry.timestamp, TIMESTAMP_FORMAT)?,
+            note: entry.note.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn entries_logged_at_once_do_not_share_an_id() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+
+        let random_bits: HashSet<i64> = (0..50)
+            .map(|_| LogEntry::new(date, 1.0, None).id & ((1 << RANDOM_ID_BITS) - 1))
+            .collect();
+
+        assert!(random_bits.len() > 1);
+    }
+
+    #[test]
+    fn ids_grow_with_time_and_stay_above_the_old_ones() {
+        let old_id = chrono::Utc::now().timestamp_millis();
+        let first = new_id();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = new_id();
+
+        assert!(old_id < first);
+        assert!(first < second);
+    }
+}
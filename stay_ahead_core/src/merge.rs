@@ -0,0 +1,304 @@
+use super::serializable_task::SerializableTask;
+use super::{LogEntry, MyTask};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/*
+ * Struct Definition
+ */
+
+// The last version that this device and the sync target agreed on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncBase {
+    pub revision: String,
+    pub tasks: Vec<SerializableTask>,
+}
+
+// A task changed on both sides in ways that cannot be combined.
+// `None` means the task was removed on that side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncConflict {
+    pub task_id: i64,
+    pub local: Option<MyTask>,
+    pub remote: Option<MyTask>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeOutcome {
+    pub tasks: HashMap<i64, MyTask>,
+    pub conflicts: Vec<SyncConflict>,
+}
+
+/*
+ * Three-way merge of task maps
+ *
+ * A task changed on only one side takes that side's version.
+ * A task changed on both sides gets its log entries combined, as long as
+ * the rest of the task was only changed on one side and no single entry was
+ * changed differently on both sides. Anything else is reported as a conflict
+ * and the version that still exists is kept, preferring the local one.
+ */
+
+pub fn merge_tasks(
+    base: &HashMap<i64, MyTask>,
+    local: &HashMap<i64, MyTask>,
+    remote: &HashMap<i64, MyTask>,
+) -> MergeOutcome {
+    let ids: HashSet<i64> = base
+        .keys()
+        .chain(local.keys())
+        .chain(remote.keys())
+        .copied()
+        .collect();
+
+    let mut tasks = HashMap::new();
+    let mut conflicts = vec![];
+
+    for id in ids {
+        let base_task = base.get(&id);
+        let local_task = local.get(&id);
+        let remote_task = remote.get(&id);
+
+        let merged = if local_task == remote_task || remote_task == base_task {
+            local_task.cloned()
+        } else if local_task == base_task {
+            remote_task.cloned()
+        } else {
+            let combined = match (local_task, remote_task) {
+                (Some(l), Some(r)) => merge_task(base_task, l, r),
+                _ => None,
+            };
+
+            if combined.is_none() {
+                conflicts.push(SyncConflict {
+                    task_id: id,
+                    local: local_task.cloned(),
+                    remote: remote_task.cloned(),
+                });
+            }
+
+            combined.or_else(|| local_task.or(remote_task).cloned())
+        };
+
+        if let Some(task) = merged {
+            tasks.insert(id, task);
+        }
+    }
+
+    conflicts.sort_by_key(|c| c.task_id);
+
+    MergeOutcome { tasks, conflicts }
+}
+
+fn merge_task(base: Option<&MyTask>, local: &MyTask, remote: &MyTask) -> Option<MyTask> {
+    let without_logs = |task: &MyTask| MyTask {
+        logs: vec![],
+        ..task.clone()
+    };
+
+    let base_meta = base.map(without_logs);
+    let local_meta = without_logs(local);
+    let remote_meta = without_logs(remote);
+
+    let mut merged = if local_meta == remote_meta || Some(&remote_meta) == base_meta.as_ref() {
+        local_meta
+    } else if Some(&local_meta) == base_meta.as_ref() {
+        remote_meta
+    } else {
+        return None;
+    };
+
+    let base_logs = base.map(|t| t.logs.as_slice()).unwrap_or_default();
+    merged.logs = merge_logs(base_logs, &local.logs, &remote.logs)?;
+
+    Some(merged)
+}
+
+fn merge_logs(base: &[LogEntry], local: &[LogEntry], remote: &[LogEntry]) -> Option<Vec<LogEntry>> {
+    let find = |logs: &[LogEntry], id: i64| logs.iter().find(|e| e.id == id).cloned();
+
+    let ids: HashSet<i64> = base
+        .iter()
+        .chain(local.iter())
+        .chain(remote.iter())
+        .map(|e| e.id)
+        .collect();
+
+    let mut merged = vec![];
+
+    for id in ids {
+        let base_entry = find(base, id);
+        let local_entry = find(local, id);
+        let remote_entry = find(remote, id);
+
+        let entry = if local_entry == remote_entry || remote_entry == base_entry {
+            local_entry
+        } else if local_entry == base_entry {
+            remote_entry
+        } else {
+            return None;
+        };
+
+        if let Some(entry) = entry {
+            merged.push(entry);
+        }
+    }
+
+    merged.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then(a.id.cmp(&b.id)));
+
+    Some(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn entry(id: i64, day: u32, amount: f32) -> LogEntry {
+        LogEntry {
+            id,
+            amount,
+            timestamp: NaiveDate::from_ymd_opt(2026, 1, day)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap(),
+            note: None,
+        }
+    }
+
+    fn task(id: i64, logs: Vec<LogEntry>) -> MyTask {
+        MyTask {
+            id,
+            action: "Read".to_string(),
+            count_per_day: 10.0,
+            unit: "pages".to_string(),
+            logs,
+            start: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            end: NaiveDate::from_ymd_opt(2026, 1, 31).unwrap(),
+            effective_dow: crate::schedule::ALL_WEEKDAYS.to_vec(),
+            daily_tasks: None,
+            name: None,
+            archive: false,
+        }
+    }
+
+    fn tasks(tasks: impl IntoIterator<Item = MyTask>) -> HashMap<i64, MyTask> {
+        tasks.into_iter().map(|task| (task.id, task)).collect()
+    }
+
+    #[test]
+    fn a_change_on_one_side_is_taken() {
+        let base = tasks([task(1, vec![]), task(2, vec![])]);
+        let mut renamed = task(1, vec![]);
+        renamed.action = "Write".to_string();
+
+        let outcome = merge_tasks(&base, &tasks([renamed.clone(), task(2, vec![])]), &base);
+        assert_eq!(outcome.tasks, tasks([renamed.clone(), task(2, vec![])]));
+        assert!(outcome.conflicts.is_empty());
+
+        let outcome = merge_tasks(&base, &base, &tasks([renamed.clone(), task(2, vec![])]));
+        assert_eq!(outcome.tasks, tasks([renamed, task(2, vec![])]));
+        assert!(outcome.conflicts.is_empty());
+    }
+
+    #[test]
+    fn tasks_added_and_removed_on_either_side_are_kept_that_way() {
+        let base = tasks([task(1, vec![]), task(2, vec![])]);
+        let local = tasks([task(1, vec![]), task(3, vec![])]);
+        let remote = tasks([task(2, vec![]), task(4, vec![])]);
+
+        let outcome = merge_tasks(&base, &local, &remote);
+
+        assert_eq!(outcome.tasks, tasks([task(3, vec![]), task(4, vec![])]));
+        assert!(outcome.conflicts.is_empty());
+    }
+
+    #[test]
+    fn logs_added_on_both_sides_are_combined_in_order() {
+        let base = tasks([task(1, vec![entry(10, 1, 5.0)])]);
+        let local = tasks([task(1, vec![entry(10, 1, 5.0), entry(30, 3, 2.0)])]);
+        let remote = tasks([task(1, vec![entry(10, 1, 5.0), entry(20, 2, 4.0)])]);
+
+        let outcome = merge_tasks(&base, &local, &remote);
+
+        assert_eq!(
+            outcome.tasks[&1].logs,
+            vec![entry(10, 1, 5.0), entry(20, 2, 4.0), entry(30, 3, 2.0)]
+        );
+        assert!(outcome.conflicts.is_empty());
+    }
+
+    #[test]
+    fn a_log_removed_on_one_side_stays_removed() {
+        let base = tasks([task(1, vec![entry(10, 1, 5.0)])]);
+        let local = tasks([task(1, vec![])]);
+        let remote = tasks([task(1, vec![entry(10, 1, 5.0), entry(20, 2, 4.0)])]);
+
+        let outcome = merge_tasks(&base, &local, &remote);
+
+        assert_eq!(outcome.tasks[&1].logs, vec![entry(20, 2, 4.0)]);
+        assert!(outcome.conflicts.is_empty());
+    }
+
+    #[test]
+    fn an_entry_changed_differently_on_both_sides_is_a_conflict_keeping_the_local_task() {
+        let base = tasks([task(1, vec![entry(10, 1, 5.0)])]);
+        let local = tasks([task(1, vec![entry(10, 1, 6.0)])]);
+        let remote = tasks([task(1, vec![entry(10, 1, 7.0)])]);
+
+        let outcome = merge_tasks(&base, &local, &remote);
+
+        assert_eq!(outcome.tasks, local);
+        assert_eq!(
+            outcome.conflicts,
+            vec![SyncConflict {
+                task_id: 1,
+                local: Some(local[&1].clone()),
+                remote: Some(remote[&1].clone()),
+            }]
+        );
+    }
+
+    #[test]
+    fn a_task_edited_on_one_side_and_removed_on_the_other_is_a_conflict_keeping_the_edit() {
+        let base = tasks([task(1, vec![])]);
+        let remote = tasks([task(1, vec![entry(10, 1, 5.0)])]);
+
+        let outcome = merge_tasks(&base, &HashMap::new(), &remote);
+
+        assert_eq!(outcome.tasks, remote);
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].local, None);
+    }
+
+    #[test]
+    fn a_new_entry_equals_its_synced_copy() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 2).unwrap();
+        let local = task(1, vec![LogEntry::new(date, 5.0, None)]);
+        let synced = MyTask::try_from(&SerializableTask::from(&local)).unwrap();
+        assert_eq!(synced, local);
+
+        // Another device edits the entry while it is unchanged here
+        let mut remote = synced.clone();
+        remote.logs[0].amount = 6.0;
+
+        let outcome = merge_tasks(&tasks([synced]), &tasks([local]), &tasks([remote.clone()]));
+
+        assert_eq!(outcome.tasks, tasks([remote]));
+        assert!(outcome.conflicts.is_empty());
+    }
+
+    #[test]
+    fn task_settings_changed_differently_on_both_sides_are_a_conflict() {
+        let base = tasks([task(1, vec![])]);
+        let mut local = task(1, vec![]);
+        local.count_per_day = 20.0;
+        let mut remote = task(1, vec![entry(10, 1, 5.0)]);
+        remote.count_per_day = 30.0;
+
+        let outcome = merge_tasks(&base, &tasks([local.clone()]), &tasks([remote]));
+
+        assert_eq!(outcome.tasks[&1], local);
+        assert_eq!(outcome.conflicts.len(), 1);
+    }
+}
@@ -0,0 +1,133 @@
+use super::log_entry::TIMESTAMP_FORMAT;
+use serde_json::{json, Map, Value};
+
+/*
+ * Schema versions of SerializableState
+ *
+ * 0: Files without `schema_version`. Gist settings at the top level, tasks
+ *    possibly without `effective_dow`, `archive` or `logs`.
+ * 1: Gist settings under `gist`, every task carries all of its fields.
+ *
+ * Changing the format means bumping SCHEMA_VERSION and appending a migration
+ * from the previous version to MIGRATIONS.
+ */
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+type Migration = fn(&mut Map<String, Value>) -> Result<(), String>;
+
+// MIGRATIONS[n] migrates from version n to n + 1
+const MIGRATIONS: [Migration; SCHEMA_VERSION as usize] = [v0_to_v1];
+
+#[derive(thiserror::Error, Debug)]
+pub enum MigrationError {
+    #[error("Failed to parse JSON state: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+
+    #[error("The data was written by a newer version of the app (schema version {0})")]
+    NewerSchema(u64),
+
+    #[error("Failed to migrate from schema version {0}: {1}")]
+    Failed(u32, String),
+}
+
+pub fn migrate(mut value: Value) -> Result<Value, MigrationError> {
+    let Some(state) = value.as_object_mut() else {
+        return Err(MigrationError::Failed(
+            0,
+            "the state is not an object".to_string(),
+        ));
+    };
+
+    let version = state
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+
+    if version > SCHEMA_VERSION as u64 {
+        return Err(MigrationError::NewerSchema(version));
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(state).map_err(|e| MigrationError::Failed(from as u32, e))?;
+    }
+
+    state.insert("schema_version".to_string(), json!(SCHEMA_VERSION));
+
+    Ok(value)
+}
+
+/*
+ * Migrations
+ */
+
+fn v0_to_v1(state: &mut Map<String, Value>) -> Result<(), String> {
+    // Gist settings used to live at the top level
+    let legacy_gist: Map<String, Value> = ["github_pat", "gist_id", "gist_file_name"]
+        .iter()
+        .filter_map(|key| state.remove(*key).map(|value| (key.to_string(), value)))
+        .collect();
+
+    if !state.contains_key("gist") && !legacy_gist.is_empty() {
+        state.insert("gist".to_string(), Value::Object(legacy_gist));
+    }
+
+    let Some(tasks) = state.get_mut("tasks").and_then(Value::as_array_mut) else {
+        return Ok(());
+    };
+
+    // A task that cannot be migrated is left as it is, to be set aside when the tasks are read
+    for task in tasks {
+        let Some(task) = task.as_object_mut() else {
+            continue;
+        };
+
+        // Tasks used to be effective on every day
+        task.entry("effective_dow").or_insert_with(|| {
+            json!([
+                "Monday",
+                "Tuesday",
+                "Wednesday",
+                "Thursday",
+                "Friday",
+                "Saturday",
+                "Sunday"
+            ])
+        });
+
+        task.entry("archive").or_insert(json!(false));
+
+        // Files written before log entries existed only carry the total.
+        // Keep it as a single entry on the start date so nothing is lost.
+        let has_logs = task
+            .get("logs")
+            .and_then(Value::as_array)
+            .is_some_and(|logs| !logs.is_empty());
+        let count_accum = task
+            .get("count_accum")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0);
+
+        if !has_logs && count_accum != 0.0 {
+            let Some(start) = task
+                .get("start")
+                .and_then(Value::as_str)
+                .and_then(|start| chrono::NaiveDate::parse_from_str(start, "%Y-%m-%d").ok())
+            else {
+                continue;
+            };
+
+            task.insert(
+                "logs".to_string(),
+                json!([{
+                    "id": task.get("id").cloned().unwrap_or(json!(0)),
+                    "amount": count_accum,
+                    "timestamp": start.and_hms_opt(0, 0, 0).unwrap().format(TIMESTAMP_FORMAT).to_string(),
+                    "note": "Progress logged before history was kept",
+                }]),
+            );
+        }
+    }
+
+    Ok(())
+}
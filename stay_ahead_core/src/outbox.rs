@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const FIRST_RETRY: Duration = Duration::from_secs(5);
+
+// Also how long it takes at most to notice that the network is back while the app stays in front
+const LONGEST_RETRY: Duration = Duration::from_secs(10 * 60);
+
+/*
+ * Struct Definition
+ */
+
+// A change that has not reached the sync target yet, kept across restarts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingChange {
+    pub reason: String,
+    pub requested: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Backoff {
+    failures: u32,
+    not_before: Option<DateTime<Utc>>,
+    rate_limited: bool,
+}
+
+/*
+ * Exponential backoff between failed pushes
+ */
+
+impl Backoff {
+    // Returns when to try again, which is never before the sync target asked for
+    pub fn failed(&mut self, retry_at: Option<DateTime<Utc>>) -> DateTime<Utc> {
+        self.failures += 1;
+
+        let delay = FIRST_RETRY
+            .saturating_mul(2_u32.saturating_pow(self.failures - 1))
+            .min(LONGEST_RETRY);
+        let backoff_until = Utc::now() + delay;
+
+        let not_before = match retry_at {
+            Some(retry_at) if retry_at > backoff_until => retry_at,
+            _ => backoff_until,
+        };
+
+        self.not_before = Some(not_before);
+        self.rate_limited = retry_at.is_some();
+
+        not_before
+    }
+
+    pub fn succeeded(&mut self) {
+        *self = Backoff::default();
+    }
+
+    // Something suggests the network is back, but a rate limit still has to be waited out
+    pub fn lift(&mut self) {
+        if !self.rate_limited {
+            self.not_before = None;
+        }
+    }
+
+    pub fn wait(&self) -> Option<Duration> {
+        self.not_before
+            .and_then(|not_before| (not_before - Utc::now()).to_std().ok())
+            .filter(|wait| !wait.is_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A little time passes between failing and measuring, so the wait is slightly shorter
+    fn assert_waits(backoff: &Backoff, expected_secs: u64) {
+        let wait = backoff.wait().unwrap();
+        assert!(
+            wait <= Duration::from_secs(expected_secs)
+                && wait > Duration::from_secs(expected_secs - 1),
+            "waits {:?} instead of {}s",
+            wait,
+            expected_secs
+        );
+    }
+
+    #[test]
+    fn each_failure_doubles_the_wait_up_to_the_longest() {
+        let mut backoff = Backoff::default();
+
+        for expected_secs in [5, 10, 20, 40, 80, 160, 320, 600, 600, 600] {
+            backoff.failed(None);
+            assert_waits(&backoff, expected_secs);
+        }
+    }
+
+    #[test]
+    fn a_later_retry_time_from_the_sync_target_wins() {
+        let mut backoff = Backoff::default();
+        let retry_at = Utc::now() + chrono::Duration::minutes(30);
+
+        assert_eq!(backoff.failed(Some(retry_at)), retry_at);
+
+        let soon = Utc::now() + chrono::Duration::seconds(1);
+        assert!(backoff.failed(Some(soon)) > soon);
+    }
+
+    #[test]
+    fn lifting_skips_the_wait_unless_rate_limited() {
+        let mut backoff = Backoff::default();
+        backoff.failed(None);
+        assert!(backoff.wait().is_some());
+        backoff.lift();
+        assert_eq!(backoff.wait(), None);
+
+        backoff.failed(Some(Utc::now() + chrono::Duration::minutes(5)));
+        backoff.lift();
+        assert!(backoff.wait().is_some());
+    }
+
+    #[test]
+    fn success_resets_the_wait() {
+        let mut backoff = Backoff::default();
+        backoff.failed(Some(Utc::now() + chrono::Duration::minutes(5)));
+        backoff.failed(None);
+        backoff.succeeded();
+
+        assert_eq!(backoff.wait(), None);
+        backoff.failed(None);
+        assert_waits(&backoff, 5);
+    }
+}
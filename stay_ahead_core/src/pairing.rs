@@ -0,0 +1,154 @@
+use super::envelope::{open, seal};
+use super::sync_backend::{GistConfig, S3Config, SyncBackendKind, WebDavConfig};
+use super::SerializableState;
+use serde::{Deserialize, Serialize};
+
+const PAIRING_VERSION: u32 = 1;
+
+const MIN_CODE_LEN: usize = 6;
+
+/*
+ * Struct Definition
+ */
+
+// Everything another device needs to sync with this one, secrets included.
+// The sync folder is left out, since its path only makes sense on this device.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pairing {
+    pub stay_ahead_pairing: u32,
+    pub sync_backend: SyncBackendKind,
+    pub gist: GistConfig,
+    pub webdav: WebDavConfig,
+    pub s3: S3Config,
+    pub sync_passphrase: Option<String>,
+}
+
+impl From<&SerializableState> for Pairing {
+    fn from(state: &SerializableState) -> Self {
+        Pairing {
+            stay_ahead_pairing: PAIRING_VERSION,
+            sync_backend: state.sync_backend,
+            gist: state.gist.clone(),
+            webdav: state.webdav.clone(),
+            s3: state.s3.clone(),
+            sync_passphrase: state.sync_passphrase.clone(),
+        }
+    }
+}
+
+/*
+ * The payload holds secrets, so it is always sealed with a pairing code
+ * that the user types on both devices
+ */
+
+impl Pairing {
+    pub fn seal(&self, code: &str) -> Result<String, String> {
+        if code.chars().count() < MIN_CODE_LEN {
+            return Err(format!(
+                "❌ The pairing code needs at least {} characters",
+                MIN_CODE_LEN
+            ));
+        }
+
+        if self.sync_backend == SyncBackendKind::Folder {
+            return Err(
+                "❌ Folder sync cannot be paired, pick the synced folder on the other device instead"
+                    .to_string(),
+            );
+        }
+
+        let json = serde_json::to_string(self)
+            .map_err(|e| format!("❌ Serialization failed: {}", e))?;
+        let sealed = seal(&json, code)?;
+
+        // Compact, so that the QR code stays easy to scan
+        serde_json::from_str::<serde_json::Value>(&sealed)
+            .map(|value| value.to_string())
+            .map_err(|e| format!("❌ Serialization failed: {}", e))
+    }
+
+    pub fn open(payload: &str, code: &str) -> Result<Self, String> {
+        if code.is_empty() {
+            return Err("❌ Enter the pairing code first".to_string());
+        }
+
+        let json = open(payload.trim(), Some(code))
+            .map_err(|_| "❌ Wrong pairing code, or not a Stay Ahead pairing QR code".to_string())?;
+
+        let pairing: Pairing = serde_json::from_str(&json)
+            .map_err(|_| "❌ This is not a Stay Ahead pairing code".to_string())?;
+
+        if pairing.stay_ahead_pairing > PAIRING_VERSION {
+            return Err(format!(
+                "❌ The pairing code was made by a newer version (version {}). Please update the app.",
+                pairing.stay_ahead_pairing
+            ));
+        }
+
+        Ok(pairing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairing() -> Pairing {
+        Pairing::from(&SerializableState {
+            sync_backend: SyncBackendKind::WebDav,
+            webdav: WebDavConfig {
+                url: Some("https://cloud.example.com/remote.php/dav/files/me".to_string()),
+                password: Some("app-password".to_string()),
+                ..Default::default()
+            },
+            sync_passphrase: Some("correct horse".to_string()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn a_sealed_pairing_opens_with_its_code() {
+        let sealed = pairing().seal("482913").unwrap();
+
+        assert!(!sealed.contains("app-password"));
+        assert!(!sealed.contains('\n'));
+        assert_eq!(Pairing::open(&sealed, "482913").unwrap(), pairing());
+    }
+
+    #[test]
+    fn a_wrong_or_missing_code_is_rejected() {
+        let sealed = pairing().seal("482913").unwrap();
+
+        assert!(Pairing::open(&sealed, "482914")
+            .unwrap_err()
+            .starts_with("❌ Wrong pairing code"));
+        assert!(Pairing::open(&sealed, "").is_err());
+    }
+
+    #[test]
+    fn a_short_code_is_rejected() {
+        assert!(pairing().seal("12345").is_err());
+    }
+
+    #[test]
+    fn folder_sync_cannot_be_paired() {
+        let mut pairing = pairing();
+        pairing.sync_backend = SyncBackendKind::Folder;
+
+        assert!(pairing.seal("482913").is_err());
+    }
+
+    #[test]
+    fn a_pairing_code_asking_for_a_costly_key_derivation_is_rejected() {
+        let sealed = pairing().seal("482913").unwrap();
+        let mut payload: serde_json::Value = serde_json::from_str(&sealed).unwrap();
+        payload["kdf"]["memory_kib"] = serde_json::json!(u32::MAX);
+
+        assert!(Pairing::open(&payload.to_string(), "482913").is_err());
+    }
+
+    #[test]
+    fn a_plain_state_is_not_a_pairing() {
+        assert!(Pairing::open(r#"{"tasks":[]}"#, "482913").is_err());
+    }
+}
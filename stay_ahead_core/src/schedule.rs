@@ -0,0 +1,414 @@
+use super::MyTask;
+use chrono::{Datelike, Days, Duration, Local, NaiveDate, Timelike, Weekday};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/*
+ * Scheduling math shared by every front-end
+ */
+
+pub static ALL_WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+// Counted in whole weeks plus the days into the last one, so that a far-off date costs
+// no more than a near one. `None` past the last date that can be represented.
+pub fn calculate_completion_date(
+    n_tasks: usize,
+    start_date: NaiveDate,
+    effective_dow: Vec<Weekday>,
+) -> Option<NaiveDate> {
+    // If no tasks, it's already complete on the start date (or before)
+    if n_tasks == 0 {
+        return Some(start_date);
+    }
+    // If no effective days, tasks can never be completed
+    if effective_dow.is_empty() {
+        return None;
+    }
+
+    // Use a HashSet so that a weekday listed twice is not counted twice
+    let effective_days_set: HashSet<Weekday> = effective_dow.iter().copied().collect();
+
+    // Every week from the start date holds each effective day exactly once
+    let per_week = effective_days_set.len();
+    let full_weeks = ((n_tasks - 1) / per_week) as u64;
+    let nth_in_last_week = (n_tasks - 1) % per_week;
+
+    let days_into_last_week = (0..7u64)
+        .filter(|&offset| {
+            let weekday = start_date.weekday().num_days_from_monday() as u64 + offset;
+            effective_days_set.contains(&ALL_WEEKDAYS[(weekday % 7) as usize])
+        })
+        .nth(nth_in_last_week)?;
+
+    let n_days = full_weeks.checked_mul(7)?.checked_add(days_into_last_week)?;
+
+    start_date.checked_add_days(Days::new(n_days))
+}
+
+pub fn generate_date_range(
+    start: NaiveDate,
+    end: NaiveDate,
+    effective_dow: Vec<Weekday>,
+) -> Vec<NaiveDate> {
+    let mut dates = vec![];
+    let mut current = start;
+    while current <= end {
+        if effective_dow.contains(&current.weekday()) {
+            dates.push(current);
+        }
+        current += Duration::days(1);
+    }
+    dates
+}
+
+pub fn fill_ratio_parallel_universe(date: NaiveDate, today: NaiveDate) -> f32 {
+    match date.cmp(&today) {
+        Ordering::Less => 1.0,
+        Ordering::Equal => {
+            let now = Local::now().time();
+            now.num_seconds_from_midnight() as f32 / 86400.0
+        }
+        Ordering::Greater => 0.0,
+    }
+}
+
+pub fn fill_ratio_user_universe(index: usize, count_per_day: f32, count_accum: f32) -> f32 {
+    let full_boxes = (count_accum / count_per_day) as usize;
+    let remaining = count_accum % count_per_day;
+
+    match index.cmp(&full_boxes) {
+        Ordering::Less => 1.0,
+        Ordering::Equal => remaining / count_per_day,
+        Ordering::Greater => 0.0,
+    }
+}
+
+/*
+ * How far the user is ahead of the Parallel Universe
+ */
+
+// Negative when behind. `days` is the lead in effective days' worth of `count_per_day`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lead {
+    pub units: f32,
+    pub days: f32,
+}
+
+impl Lead {
+    pub fn describe(&self, unit: &str) -> String {
+        if self.units.abs() < 0.05 {
+            return "Neck and neck with the Parallel Universe".to_string();
+        }
+
+        format!(
+            "{:.1} day(s) {} ({:+.1} {})",
+            self.days.abs(),
+            if self.units > 0.0 { "ahead" } else { "behind" },
+            self.units,
+            unit
+        )
+    }
+}
+
+// The Parallel Universe fills today's box as the day goes, so the lead shrinks through the day
+pub fn task_lead(task: &MyTask, today: NaiveDate) -> Lead {
+    let planned_days = generate_date_range(task.start, task.end, task.effective_dow.clone())
+        .into_iter()
+        .fold(0.0, |sum, date| sum + fill_ratio_parallel_universe(date, today));
+
+    let units = task.count_accum() - planned_days * task.count_per_day;
+
+    Lead {
+        units,
+        days: if task.count_per_day > 0.0 {
+            units / task.count_per_day
+        } else {
+            0.0
+        },
+    }
+}
+
+/*
+ * Forecast from the pace so far
+ */
+
+// A required pace above this many times `count_per_day` means the goal is slipping
+pub const DEFAULT_PACE_WARNING_MULTIPLE: f32 = 1.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Projection {
+    Done,
+    On(NaiveDate),
+    // Nothing logged yet, or no effective day left to log on
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Forecast {
+    // Logged per effective day before today
+    pub pace: f32,
+    pub projected_finish: Projection,
+    // Needed per effective day from today on, `None` once the last one has passed
+    pub required_pace: Option<f32>,
+    pub days_left: usize,
+}
+
+impl Forecast {
+    pub fn is_slipping(&self, count_per_day: f32, warning_multiple: f32) -> bool {
+        match self.required_pace {
+            Some(required_pace) => required_pace > count_per_day * warning_multiple,
+            None => self.projected_finish != Projection::Done,
+        }
+    }
+}
+
+// Today counts as a day left, since it can still be logged on, so it is not part of the pace
+pub fn task_forecast(task: &MyTask, today: NaiveDate) -> Forecast {
+    let dates = generate_date_range(task.start, task.end, task.effective_dow.clone());
+    let total = dates.len() as f32 * task.count_per_day;
+    let accomplished = task.count_accum();
+    let remaining = total - accomplished;
+
+    let days_passed = dates.iter().filter(|&&date| date < today).count();
+    let days_left = dates.len() - days_passed;
+
+    let pace = if days_passed > 0 {
+        let accomplished_before_today = task
+            .logs
+            .iter()
+            .filter(|entry| entry.timestamp.date() < today)
+            .fold(0.0, |sum, entry| sum + entry.amount);
+
+        accomplished_before_today / days_passed as f32
+    } else {
+        0.0
+    };
+
+    let projected_finish = if remaining <= 0.0 {
+        Projection::Done
+    } else if pace <= 0.0 {
+        Projection::Never
+    } else {
+        let days_needed = (remaining / pace).ceil() as usize;
+        calculate_completion_date(days_needed, today.max(task.start), task.effective_dow.clone())
+            .map_or(Projection::Never, Projection::On)
+    };
+
+    Forecast {
+        pace,
+        projected_finish,
+        required_pace: (days_left > 0).then(|| remaining.max(0.0) / days_left as f32),
+        days_left,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LogEntry;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn task(start: NaiveDate, end: NaiveDate, count_per_day: f32) -> MyTask {
+        MyTask {
+            id: 1,
+            action: "Walk".to_string(),
+            count_per_day,
+            unit: "steps".to_string(),
+            logs: vec![],
+            start,
+            end,
+            effective_dow: ALL_WEEKDAYS.to_vec(),
+            daily_tasks: None,
+            name: None,
+            archive: false,
+        }
+    }
+
+    // The nth effective day, found one day at a time
+    fn completion_date_day_by_day(
+        n_tasks: usize,
+        start: NaiveDate,
+        effective_dow: &[Weekday],
+    ) -> NaiveDate {
+        start
+            .iter_days()
+            .filter(|d| effective_dow.contains(&d.weekday()))
+            .nth(n_tasks.saturating_sub(1))
+            .filter(|_| n_tasks > 0)
+            .unwrap_or(start)
+    }
+
+    #[test]
+    fn completion_date_matches_counting_day_by_day() {
+        let weekday_sets: [&[Weekday]; 4] = [
+            &ALL_WEEKDAYS,
+            &[Weekday::Mon, Weekday::Wed, Weekday::Fri],
+            &[Weekday::Sun],
+            &[Weekday::Sat, Weekday::Sat, Weekday::Tue],
+        ];
+
+        for effective_dow in weekday_sets {
+            for start in date(2026, 3, 1).iter_days().take(7) {
+                for n_tasks in 0..40 {
+                    assert_eq!(
+                        calculate_completion_date(n_tasks, start, effective_dow.to_vec()),
+                        Some(completion_date_day_by_day(n_tasks, start, effective_dow)),
+                        "{} task(s) from {} on {:?}",
+                        n_tasks,
+                        start,
+                        effective_dow
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn completion_date_without_effective_days_is_never() {
+        assert_eq!(calculate_completion_date(3, date(2026, 3, 1), vec![]), None);
+    }
+
+    #[test]
+    fn completion_date_past_the_last_representable_date_is_none() {
+        assert_eq!(
+            calculate_completion_date(usize::MAX, date(2026, 3, 1), vec![Weekday::Mon]),
+            None
+        );
+    }
+
+    #[test]
+    fn forecast_at_a_tiny_pace_is_never_instead_of_a_panic() {
+        let today = date(2026, 10, 28);
+        let mut task = task(
+            today - Duration::days(300),
+            today + Duration::days(65),
+            10000.0,
+        );
+        task.logs.push(LogEntry::new(task.start, 1.0, None));
+
+        let forecast = task_forecast(&task, today);
+
+        assert_eq!(forecast.projected_finish, Projection::Never);
+        assert_eq!(forecast.days_left, 66);
+    }
+
+    #[test]
+    fn lead_counts_finished_days_and_the_share_of_today() {
+        let start = date(2026, 3, 1);
+        let mut task = task(start, date(2026, 3, 31), 10.0);
+        task.logs.push(LogEntry::new(start, 35.0, None));
+
+        // Three days have fully passed, today has only just begun in the Parallel Universe
+        let lead = task_lead(&task, date(2026, 3, 4));
+        assert!(lead.units <= 5.0 && lead.units > -5.0);
+        assert_eq!(lead.days, lead.units / 10.0);
+
+        // Nothing is planned before the start
+        let lead = task_lead(&task, date(2026, 2, 1));
+        assert_eq!(
+            lead,
+            Lead {
+                units: 35.0,
+                days: 3.5
+            }
+        );
+        assert_eq!(lead.describe("pages"), "3.5 day(s) ahead (+35.0 pages)");
+    }
+
+    #[test]
+    fn lead_is_zero_days_without_a_daily_amount() {
+        let task = task(date(2026, 3, 1), date(2026, 3, 31), 0.0);
+
+        assert_eq!(task_lead(&task, date(2026, 3, 10)).days, 0.0);
+    }
+
+    #[test]
+    fn forecast_projects_the_pace_so_far() {
+        let start = date(2026, 3, 1);
+        let mut task = task(start, date(2026, 3, 10), 10.0);
+        task.logs.push(LogEntry::new(start, 25.0, None));
+
+        // 25 over the 4 days before today is 6.25 a day, the remaining 75 take 12 days from today
+        let forecast = task_forecast(&task, date(2026, 3, 5));
+
+        assert_eq!(forecast.pace, 6.25);
+        assert_eq!(forecast.days_left, 6);
+        assert_eq!(forecast.required_pace, Some(12.5));
+        assert_eq!(forecast.projected_finish, Projection::On(date(2026, 3, 16)));
+        assert!(forecast.is_slipping(10.0, 1.2));
+        assert!(!forecast.is_slipping(10.0, DEFAULT_PACE_WARNING_MULTIPLE));
+    }
+
+    #[test]
+    fn forecast_is_done_once_everything_is_logged() {
+        let start = date(2026, 3, 1);
+        let mut task = task(start, date(2026, 3, 10), 10.0);
+        task.logs.push(LogEntry::new(start, 100.0, None));
+
+        let forecast = task_forecast(&task, date(2026, 3, 20));
+
+        assert_eq!(forecast.projected_finish, Projection::Done);
+        assert_eq!(forecast.required_pace, None);
+        assert!(!forecast.is_slipping(10.0, DEFAULT_PACE_WARNING_MULTIPLE));
+    }
+
+    #[test]
+    fn forecast_without_logs_is_never_and_slips_after_the_end() {
+        let task = task(date(2026, 3, 1), date(2026, 3, 10), 10.0);
+
+        let forecast = task_forecast(&task, date(2026, 3, 20));
+
+        assert_eq!(forecast.projected_finish, Projection::Never);
+        assert_eq!(forecast.days_left, 0);
+        assert!(forecast.is_slipping(10.0, DEFAULT_PACE_WARNING_MULTIPLE));
+    }
+
+    #[test]
+    fn the_last_day_of_a_task_ending_today_is_still_left() {
+        let start = date(2026, 3, 1);
+        let today = date(2026, 3, 10);
+        let mut task = task(start, today, 10.0);
+        task.logs.push(LogEntry::new(start, 80.0, None));
+
+        let forecast = task_forecast(&task, today);
+
+        assert_eq!(forecast.pace, 80.0 / 9.0);
+        assert_eq!(forecast.days_left, 1);
+        assert_eq!(forecast.required_pace, Some(20.0));
+        assert_eq!(forecast.projected_finish, Projection::On(date(2026, 3, 12)));
+        assert!(forecast.is_slipping(10.0, DEFAULT_PACE_WARNING_MULTIPLE));
+
+        // What is logged today counts towards what is left, not towards the pace
+        task.logs.push(LogEntry::new(today, 10.0, None));
+        let forecast = task_forecast(&task, today);
+
+        assert_eq!(forecast.pace, 80.0 / 9.0);
+        assert_eq!(forecast.required_pace, Some(10.0));
+        assert!(!forecast.is_slipping(10.0, DEFAULT_PACE_WARNING_MULTIPLE));
+    }
+
+    #[test]
+    fn the_pace_starts_after_the_first_day() {
+        let start = date(2026, 3, 1);
+        let mut task = task(start, date(2026, 3, 10), 10.0);
+        task.logs.push(LogEntry::new(start, 10.0, None));
+
+        let forecast = task_forecast(&task, start);
+
+        assert_eq!(forecast.pace, 0.0);
+        assert_eq!(forecast.projected_finish, Projection::Never);
+        assert_eq!(forecast.days_left, 10);
+        assert_eq!(forecast.required_pace, Some(9.0));
+    }
+}
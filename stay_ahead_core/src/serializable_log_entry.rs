@@ -0,0 +1,31 @@
+use super::log_entry::{LogEntry, TIMESTAMP_FORMAT};
+use serde::{Deserialize, Serialize};
+
+/*
+ * Struct Definition
+ */
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SerializableLogEntry {
+    pub id: i64,
+    pub amount: f32,
+    pub timestamp: String,
+
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/*
+ * Conversion from LogEntry to SerializableLogEntry
+ */
+
+impl From<&LogEntry> for SerializableLogEntry {
+    fn from(entry: &LogEntry) -> Self {
+        Self {
+            id: entry.id,
+            amount: entry.amount,
+            timestamp: entry.timestamp.format(TIMESTAMP_FORMAT).to_string(),
+            note: entry.note.clone(),
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use super::load_error::QuarantinedTask;
+use super::merge::SyncBase;
+use super::outbox::PendingChange;
+use super::migration::{migrate, MigrationError};
+use super::serializable_task::SerializableTask;
+use super::sync_backend::{FolderConfig, GistConfig, S3Config, SyncBackendKind, WebDavConfig};
+use super::encoding::{decrypt, encrypt, KEY_LEN};
+use super::MyTask;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/*
+ * Struct Definition
+ */
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SerializableState {
+    #[serde(default)]
+    pub schema_version: u32,
+
+    pub tasks: Option<Vec<SerializableTask>>,
+
+    #[serde(default)]
+    pub sync_backend: SyncBackendKind,
+
+    #[serde(default)]
+    pub gist: GistConfig,
+
+    #[serde(default)]
+    pub webdav: WebDavConfig,
+
+    #[serde(default)]
+    pub s3: S3Config,
+
+    #[serde(default)]
+    pub folder: FolderConfig,
+
+    // Number of local backups to keep, the default when not set
+    #[serde(default)]
+    pub backup_count: Option<usize>,
+
+    // Multiple of a task's daily amount above which its required pace is warned about
+    #[serde(default)]
+    pub pace_warning_multiple: Option<f32>,
+
+    // Shared by all devices out of band, never part of what gets pushed
+    #[serde(default)]
+    pub sync_passphrase: Option<String>,
+
+    // Only kept locally, never part of what gets pushed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_base: Option<SyncBase>,
+
+    // Tasks that could not be read, kept so that nothing is lost
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quarantine: Vec<QuarantinedTask>,
+
+    // Changes still waiting to be pushed, only kept locally
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sync_outbox: Vec<PendingChange>,
+}
+
+/*
+ * Parsing, with older formats migrated to the current schema first
+ */
+
+impl SerializableState {
+    pub fn from_json(json: &str) -> Result<Self, MigrationError> {
+        Self::from_value(serde_json::from_str(json)?)
+    }
+
+    // A task that does not parse is set aside instead of failing the whole state
+    pub fn from_value(value: serde_json::Value) -> Result<Self, MigrationError> {
+        let mut value = migrate(value)?;
+        let raw_tasks = value.as_object_mut().and_then(|state| state.remove("tasks"));
+
+        let mut state: Self = serde_json::from_value(value)?;
+
+        if let Some(serde_json::Value::Array(raw_tasks)) = raw_tasks {
+            let mut tasks = vec![];
+
+            for raw in raw_tasks {
+                match serde_json::from_value::<SerializableTask>(raw.clone()) {
+                    Ok(task) => tasks.push(task),
+                    Err(e) => state.quarantine.push(QuarantinedTask {
+                        raw,
+                        error: e.to_string(),
+                    }),
+                }
+            }
+
+            state.tasks = Some(tasks);
+        }
+
+        Ok(state)
+    }
+}
+
+/*
+ * Secrets are encrypted with a device key at rest and only decrypted in memory.
+ * They are never part of exports or of what gets pushed.
+ */
+
+impl SerializableState {
+    fn secrets_mut(&mut self) -> [&mut Option<String>; 4] {
+        [
+            &mut self.gist.github_pat,
+            &mut self.webdav.password,
+            &mut self.s3.secret_access_key,
+            &mut self.sync_passphrase,
+        ]
+    }
+
+    pub fn strip_secrets(&mut self) {
+        for secret in self.secrets_mut() {
+            *secret = None;
+        }
+    }
+
+    pub fn encrypt_secrets(&mut self, key: &[u8; KEY_LEN]) {
+        for secret in self.secrets_mut() {
+            *secret = secret.as_ref().and_then(|value| match encrypt(value, key) {
+                Ok(encrypted) => Some(encrypted),
+                Err(e) => {
+                    log::error!("A secret is not saved: {}", e);
+                    None
+                }
+            });
+        }
+    }
+
+    pub fn decrypt_secrets(&mut self, key: &[u8; KEY_LEN]) {
+        for secret in self.secrets_mut() {
+            *secret = secret.as_ref().and_then(|value| {
+                let decrypted = decrypt(value, key);
+                if decrypted.is_none() {
+                    log::warn!("A secret could not be decrypted on this device and was dropped");
+                }
+                decrypted
+            });
+        }
+    }
+
+    // Imports come without secrets, so the ones already entered on this device are kept
+    pub fn keep_secrets_from(&mut self, current: &SerializableState) {
+        let mut current = current.clone();
+
+        for (secret, current_secret) in self.secrets_mut().into_iter().zip(current.secrets_mut()) {
+            if secret.is_none() {
+                *secret = current_secret.take();
+            }
+        }
+    }
+}
+
+/*
+ * Tasks in the order they are saved in
+ */
+
+impl SerializableState {
+    // Tasks that fail to convert are quarantined so the others still load
+    pub fn read_tasks(&mut self) -> Option<HashMap<i64, MyTask>> {
+        let quarantine = &mut self.quarantine;
+
+        self.tasks.as_ref().map(|tasks| {
+            tasks
+                .iter()
+                .filter_map(|t| match MyTask::try_from(t) {
+                    Ok(task) => Some((task.id, task)),
+                    Err(e) => {
+                        log::warn!("Task {} was set aside: {}", t.id, e);
+                        quarantine.push(QuarantinedTask {
+                            raw: serde_json::to_value(t).unwrap_or_default(),
+                            error: e.to_string(),
+                        });
+                        None
+                    }
+                })
+                .collect()
+        })
+    }
+
+    // Sorted so that unchanged tasks always serialize the same way
+    pub fn serialize_tasks(tasks: &HashMap<i64, MyTask>) -> Vec<SerializableTask> {
+        let mut tasks: Vec<SerializableTask> = tasks.values().map(SerializableTask::from).collect();
+        tasks.sort_by_key(|task| task.id);
+        tasks
+    }
+}
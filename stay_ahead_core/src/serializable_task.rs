@@ -1,3 +1,4 @@
+use super::serializable_log_entry::SerializableLogEntry;
 use super::MyTask;
 use serde::{Deserialize, Serialize};
 
@@ -11,7 +12,13 @@ pub struct SerializableTask {
     pub action: String,
     pub count_per_day: f32,
     pub unit: String,
+
+    // Kept in sync with the sum of `logs` so older files remain readable
     pub count_accum: f32,
+
+    #[serde(default)]
+    pub logs: Vec<SerializableLogEntry>,
+
     pub start: String,
     pub end: String,
 
@@ -51,7 +58,8 @@ impl From<&MyTask> for SerializableTask {
             action: task.action.clone(),
             count_per_day: task.count_per_day,
             unit: task.unit.clone(),
-            count_accum: task.count_accum,
+            count_accum: task.count_accum(),
+            logs: task.logs.iter().map(SerializableLogEntry::from).collect(),
             start: task.start.to_string(),
             end: task.end.to_string(),
             effective_dow: task.effective_dow.iter().map(|d| d.to_string()).collect(),
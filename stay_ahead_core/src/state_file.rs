@@ -0,0 +1,179 @@
+use super::encoding::{generate_key, KEY_LEN};
+use super::load_error::LoadError;
+use super::SerializableState;
+use base64::{engine::general_purpose, Engine as _};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub const STORAGE_KEY: &str = "PaleggWorks_StayAhead_AppState";
+
+/*
+ * The state file on devices with a file system
+ *
+ * The app and the command line read and write the same file. Secrets in it
+ * are encrypted with a device key kept in a file next to it.
+ */
+
+pub fn state_file_in(dir: &Path) -> PathBuf {
+    dir.join(format!("{}.json", STORAGE_KEY))
+}
+
+pub fn load(path: &Path) -> Result<SerializableState, LoadError> {
+    load_with_digest(path).map(|(state, _)| state)
+}
+
+// Along with the digest of the contents it was read from, see `digest_on_disk`
+pub fn load_with_digest(path: &Path) -> Result<(SerializableState, String), LoadError> {
+    let data = std::fs::read_to_string(path)?;
+    let mut parsed = SerializableState::from_json(&data)?;
+
+    match device_key(path) {
+        Ok(key) => parsed.decrypt_secrets(&key),
+        Err(e) => {
+            log::error!("Secrets cannot be read: {}", e);
+            parsed.strip_secrets();
+        }
+    }
+
+    Ok((parsed, digest(&data)))
+}
+
+pub fn save(path: &Path, state: &SerializableState) -> Result<(), String> {
+    let mut state = state.clone();
+
+    match device_key(path) {
+        Ok(key) => state.encrypt_secrets(&key),
+        Err(e) => {
+            log::error!("Secrets are not saved: {}", e);
+            state.strip_secrets();
+        }
+    }
+
+    let json = serde_json::to_string_pretty(&state)
+        .map_err(|e| format!("Failed to serialize the app state: {}", e))?;
+
+    write_atomically(path, &json)
+}
+
+// Written next to the file and renamed into place, so a crash never truncates it.
+// The contents reach the disk before the rename, and the rename before returning,
+// or a power loss could still leave an empty file behind.
+pub fn write_atomically(path: &Path, contents: &str) -> Result<(), String> {
+    let temp_path = path.with_extension("json.tmp");
+
+    write_durably(&temp_path, contents)
+        .and_then(|_| std::fs::rename(&temp_path, path))
+        .and_then(|_| sync_parent_dir(path))
+        .map_err(|e| format!("Failed to save to {}: {}", path.display(), e))
+}
+
+fn write_durably(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()
+}
+
+// Directories cannot be opened for syncing on Windows, where the rename is durable anyway
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) -> std::io::Result<()> {
+    match path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        Some(dir) => std::fs::File::open(dir)?.sync_all(),
+        None => std::fs::File::open(".")?.sync_all(),
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/*
+ * Noticing writes by another program
+ *
+ * The app keeps the state in memory while it runs, and the command line can
+ * change the file in the meantime. The digest of what was last read or
+ * written is compared with the one on disk before saving over it.
+ */
+
+pub fn digest(contents: &str) -> String {
+    Sha256::digest(contents.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+// `None` when there is no file to read
+pub fn digest_on_disk(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|contents| digest(&contents))
+}
+
+/*
+ * Device-local key used to encrypt secrets at rest
+ *
+ * It is created on first use and never leaves this device, so secrets
+ * in exports or on the sync target could not be read elsewhere anyway.
+ */
+
+pub fn device_key(state_file: &Path) -> Result<[u8; KEY_LEN], String> {
+    let path = state_file.with_extension("key");
+
+    if let Some(key) = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|encoded| parse_device_key(&encoded))
+    {
+        return Ok(key);
+    }
+
+    let key = generate_key()?;
+
+    std::fs::write(&path, encode_device_key(&key))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    }
+
+    Ok(key)
+}
+
+pub fn encode_device_key(key: &[u8; KEY_LEN]) -> String {
+    general_purpose::STANDARD.encode(key)
+}
+
+pub fn parse_device_key(encoded: &str) -> Option<[u8; KEY_LEN]> {
+    general_purpose::STANDARD
+        .decode(encoded.trim())
+        .ok()?
+        .try_into()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_digest_on_disk_follows_every_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = state_file_in(dir.path());
+
+        assert_eq!(digest_on_disk(&path), None);
+
+        save(&path, &SerializableState::default()).unwrap();
+        let (_, loaded) = load_with_digest(&path).unwrap();
+        assert_eq!(digest_on_disk(&path), Some(loaded.clone()));
+
+        // Another program writes the same file
+        write_atomically(&path, r#"{"schema_version":1,"tasks":[]}"#).unwrap();
+        assert_ne!(digest_on_disk(&path), Some(loaded));
+        assert_eq!(
+            digest_on_disk(&path),
+            Some(digest(r#"{"schema_version":1,"tasks":[]}"#))
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use super::schedule::generate_date_range;
+use super::MyTask;
+use chrono::NaiveDate;
+
+// Amounts are summed floats, so a day counts as met when it is this close
+const TOLERANCE: f32 = 1e-4;
+
+/*
+ * Struct Definition
+ */
+
+// Counted in effective days, days off do not break a streak
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Streak {
+    pub current: usize,
+    pub longest: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Streaks {
+    // Days on which the day's `count_per_day` was logged
+    pub daily: Streak,
+    // Days that ended with the user ahead of the Parallel Universe, carry-over included
+    pub ahead: Streak,
+}
+
+impl Streaks {
+    pub fn describe(&self) -> String {
+        format!(
+            "🔥 {} day streak (best {}) · ⏩ {} day(s) ahead in a row (best {})",
+            self.daily.current, self.daily.longest, self.ahead.current, self.ahead.longest
+        )
+    }
+}
+
+/*
+ * Streaks from what was logged on each day
+ */
+
+pub fn task_streaks(task: &MyTask, today: NaiveDate) -> Streaks {
+    let dates = generate_date_range(task.start, task.end.min(today), task.effective_dow.clone());
+    let per_day = task.logged_per_day();
+
+    let daily: Vec<(NaiveDate, bool)> = dates
+        .iter()
+        .map(|date| {
+            let logged = per_day.get(date).copied().unwrap_or(0.0);
+            (*date, logged + TOLERANCE >= task.count_per_day)
+        })
+        .collect();
+
+    // Everything logged up to the end of each day, against a full box per effective day so far
+    let mut logged_days: Vec<(NaiveDate, f32)> = per_day.into_iter().collect();
+    logged_days.sort_by_key(|(date, _)| *date);
+
+    let mut logged_days = logged_days.into_iter().peekable();
+    let mut logged_so_far = 0.0;
+
+    let ahead: Vec<(NaiveDate, bool)> = dates
+        .iter()
+        .enumerate()
+        .map(|(i, date)| {
+            while let Some((_, amount)) = logged_days.next_if(|(logged_on, _)| logged_on <= date) {
+                logged_so_far += amount;
+            }
+
+            let planned = (i + 1) as f32 * task.count_per_day;
+            (*date, logged_so_far + TOLERANCE >= planned)
+        })
+        .collect();
+
+    Streaks {
+        daily: streak_of(&daily, today),
+        ahead: streak_of(&ahead, today),
+    }
+}
+
+// Today is still going, so not having made it yet does not end the current streak
+fn streak_of(days: &[(NaiveDate, bool)], today: NaiveDate) -> Streak {
+    let mut longest = 0;
+    let mut run = 0;
+
+    for (_, made_it) in days {
+        if *made_it {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+
+    let settled = match days.split_last() {
+        Some(((date, false), earlier)) if *date == today => earlier,
+        _ => days,
+    };
+
+    Streak {
+        current: settled.iter().rev().take_while(|(_, made_it)| *made_it).count(),
+        longest,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LogEntry;
+    use chrono::Weekday;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    // March 2026, on weekdays only, 10 a day
+    fn task(logged: &[(u32, f32)]) -> MyTask {
+        MyTask {
+            id: 1,
+            action: "Read".to_string(),
+            count_per_day: 10.0,
+            unit: "pages".to_string(),
+            logs: logged
+                .iter()
+                .map(|(day, amount)| LogEntry::new(date(*day), *amount, None))
+                .collect(),
+            start: date(2),
+            end: date(31),
+            effective_dow: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            daily_tasks: None,
+            name: None,
+            archive: false,
+        }
+    }
+
+    #[test]
+    fn days_off_do_not_break_a_streak() {
+        // Thursday 5th to Tuesday 10th, over the weekend
+        let task = task(&[(5, 10.0), (6, 10.0), (9, 10.0), (10, 10.0)]);
+
+        let streaks = task_streaks(&task, date(10));
+
+        assert_eq!(
+            streaks.daily,
+            Streak {
+                current: 4,
+                longest: 4
+            }
+        );
+    }
+
+    #[test]
+    fn today_not_made_yet_keeps_the_current_streak() {
+        let task = task(&[(2, 10.0), (3, 10.0)]);
+
+        assert_eq!(task_streaks(&task, date(4)).daily.current, 2);
+        assert_eq!(
+            task_streaks(&task, date(5)).daily,
+            Streak {
+                current: 0,
+                longest: 2
+            }
+        );
+    }
+
+    #[test]
+    fn being_ahead_carries_over_to_the_next_days() {
+        // 30 on Monday covers until Wednesday, Thursday falls behind
+        let task = task(&[(2, 30.0), (6, 5.0)]);
+
+        let streaks = task_streaks(&task, date(6));
+
+        assert_eq!(
+            streaks.daily,
+            Streak {
+                current: 0,
+                longest: 1
+            }
+        );
+        assert_eq!(
+            streaks.ahead,
+            Streak {
+                current: 0,
+                longest: 3
+            }
+        );
+    }
+
+    #[test]
+    fn amounts_adding_up_to_the_daily_one_count() {
+        let task = task(&[(2, 0.1), (2, 0.2), (2, 9.7)]);
+
+        assert_eq!(task_streaks(&task, date(2)).daily.current, 1);
+    }
+}
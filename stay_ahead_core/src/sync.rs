@@ -0,0 +1,340 @@
+use super::envelope::{open, seal};
+use super::load_error::TaskError;
+use super::merge::{merge_tasks, SyncBase, SyncConflict};
+use super::serializable_task::SerializableTask;
+use super::sync_backend::{SyncBackend, SyncError};
+use super::{MyTask, SerializableState};
+use std::collections::HashMap;
+
+/*
+ * What syncing needs to read and write on this device
+ *
+ * Tasks are read again right before a merge and written right after it, with
+ * nothing awaited in between, so edits made while a sync is running are kept.
+ */
+
+pub trait LocalState {
+    fn get_tasks(&self) -> Option<HashMap<i64, MyTask>>;
+
+    fn set_tasks(&mut self, tasks: Option<HashMap<i64, MyTask>>);
+
+    fn get_sync_base(&self) -> Option<SyncBase>;
+
+    fn set_sync_base(&mut self, sync_base: Option<SyncBase>);
+
+    fn passphrase(&self) -> Option<String>;
+
+    // The whole state, of which only what is safe to share gets pushed
+    fn to_serializable(&self) -> SerializableState;
+}
+
+/*
+ * Pulling and pushing
+ */
+
+pub async fn pull<S: LocalState, B: SyncBackend>(
+    state: &mut S,
+    backend: &B,
+) -> Result<Vec<SyncConflict>, String> {
+    let remote = backend
+        .fetch()
+        .await
+        .map_err(|e| format!("❌ Failed to pull: {}", e))?;
+
+    let remote_tasks = parse_remote(state, &remote.content)?;
+
+    merge_remote(state, remote_tasks, remote.revision)
+}
+
+pub async fn push<S: LocalState, B: SyncBackend>(
+    state: &mut S,
+    backend: &B,
+) -> Result<Vec<SyncConflict>, SyncError> {
+    if !backend.is_configured() {
+        return Err("❌ Sync is not configured".to_string().into());
+    }
+
+    // Another device may have pushed since our last sync, so bring its changes in first
+    let revision = backend
+        .revision()
+        .await
+        .map_err(|e| format!("❌ Failed to check the sync target before pushing: {}", e))?;
+
+    let base_revision = state.get_sync_base().map(|base| base.revision);
+
+    let (conflicts, target_empty) = if base_revision.as_ref() == Some(&revision) {
+        (vec![], false)
+    } else {
+        let remote = backend
+            .fetch()
+            .await
+            .map_err(|e| format!("❌ Failed to check the sync target before pushing: {}", e))?;
+
+        if remote.content.trim().is_empty() {
+            (vec![], true)
+        } else {
+            let remote_tasks = parse_remote(state, &remote.content)?;
+            (merge_remote(state, remote_tasks, remote.revision)?, false)
+        }
+    };
+
+    let (json, tasks) = sync_payload(state)?;
+
+    // Nothing is there to overwrite, and a base from another target would never match
+    let expected_revision = if target_empty {
+        None
+    } else {
+        state.get_sync_base().map(|base| base.revision)
+    };
+    let new_revision = backend.store(json, expected_revision).await?;
+
+    state.set_sync_base(Some(SyncBase {
+        revision: new_revision,
+        tasks,
+    }));
+
+    Ok(conflicts)
+}
+
+// What gets stored on the sync target, along with the tasks it holds
+pub fn sync_payload<S: LocalState>(state: &S) -> Result<(String, Vec<SerializableTask>), String> {
+    let mut serializable = state.to_serializable();
+
+    // Pushed without them, they would be removed from the sync target for every device
+    if !serializable.quarantine.is_empty() {
+        return Err(format!(
+            "❌ Nothing was pushed, {} task(s) on this device could not be read. Export or discard them on the task list first",
+            serializable.quarantine.len()
+        ));
+    }
+
+    serializable.strip_secrets();
+    serializable.sync_base = None;
+    serializable.sync_outbox = vec![];
+
+    let json = serde_json::to_string_pretty(&serializable)
+        .map_err(|e| format!("❌ Serialization failed: {}", e))?;
+
+    let json = match state.passphrase().filter(|p| !p.is_empty()) {
+        Some(passphrase) => seal(&json, &passphrase)?,
+        None => json,
+    };
+
+    Ok((json, serializable.tasks.unwrap_or_default()))
+}
+
+// Reads a state as found on the sync target or in its history
+pub fn read_remote(content: &str, passphrase: Option<&str>) -> Result<SerializableState, String> {
+    let content = open(content, passphrase)?;
+
+    SerializableState::from_json(&content)
+        .map_err(|e| format!("❌ Failed to read the synced data: {}", e))
+}
+
+// Merging without an unreadable task would look like it was removed on the other side
+fn parse_remote<S: LocalState>(
+    state: &S,
+    content: &str,
+) -> Result<Option<Vec<SerializableTask>>, String> {
+    let parsed = read_remote(content, state.passphrase().as_deref())?;
+
+    if !parsed.quarantine.is_empty() {
+        return Err(format!(
+            "❌ The synced data has {} unreadable task(s), nothing was merged",
+            parsed.quarantine.len()
+        ));
+    }
+
+    Ok(parsed.tasks)
+}
+
+fn merge_remote<S: LocalState>(
+    state: &mut S,
+    remote_tasks: Option<Vec<SerializableTask>>,
+    revision: String,
+) -> Result<Vec<SyncConflict>, String> {
+    let base = state.get_sync_base();
+
+    // Nothing was pushed from elsewhere since our last sync
+    if base.as_ref().is_some_and(|base| base.revision == revision) {
+        return Ok(vec![]);
+    }
+
+    let remote_tasks = remote_tasks.unwrap_or_default();
+
+    let base_tasks = tasks_by_id(&base.map(|base| base.tasks).unwrap_or_default())
+        .map_err(|e| format!("❌ Failed to read the last synced version: {}", e))?;
+    let remote = tasks_by_id(&remote_tasks)
+        .map_err(|e| format!("❌ Failed to read the synced tasks: {}", e))?;
+    let current = state.get_tasks();
+    let local = current.clone().unwrap_or_default();
+
+    let outcome = merge_tasks(&base_tasks, &local, &remote);
+
+    if current.is_some() || !outcome.tasks.is_empty() {
+        state.set_tasks(Some(outcome.tasks));
+    }
+
+    state.set_sync_base(Some(SyncBase {
+        revision,
+        tasks: remote_tasks,
+    }));
+
+    Ok(outcome.conflicts)
+}
+
+fn tasks_by_id(tasks: &[SerializableTask]) -> Result<HashMap<i64, MyTask>, TaskError> {
+    tasks
+        .iter()
+        .map(|t| MyTask::try_from(t).map(|task| (task.id, task)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_error::QuarantinedTask;
+    use crate::sync_backend::RemoteContent;
+    use chrono::NaiveDate;
+    use std::cell::RefCell;
+
+    // A sync target kept in memory, where every write makes a new revision
+    #[derive(Default)]
+    struct MemoryBackend {
+        remote: RefCell<RemoteContent>,
+        stores: RefCell<Vec<Option<String>>>,
+    }
+
+    impl SyncBackend for MemoryBackend {
+        fn is_configured(&self) -> bool {
+            true
+        }
+
+        async fn fetch(&self) -> Result<RemoteContent, String> {
+            Ok(self.remote.borrow().clone())
+        }
+
+        async fn store(
+            &self,
+            content: String,
+            expected_revision: Option<String>,
+        ) -> Result<String, SyncError> {
+            let mut remote = self.remote.borrow_mut();
+
+            if expected_revision.as_ref().is_some_and(|r| *r != remote.revision) {
+                return Err("❌ The sync target changed".to_string().into());
+            }
+
+            self.stores.borrow_mut().push(expected_revision);
+            remote.revision = format!("rev-{}", self.stores.borrow().len());
+            remote.content = content;
+
+            Ok(remote.revision.clone())
+        }
+
+        async fn revision(&self) -> Result<String, String> {
+            Ok(self.remote.borrow().revision.clone())
+        }
+    }
+
+    #[derive(Default)]
+    struct TestState {
+        tasks: Option<HashMap<i64, MyTask>>,
+        sync_base: Option<SyncBase>,
+        quarantine: Vec<QuarantinedTask>,
+    }
+
+    impl LocalState for TestState {
+        fn get_tasks(&self) -> Option<HashMap<i64, MyTask>> {
+            self.tasks.clone()
+        }
+
+        fn set_tasks(&mut self, tasks: Option<HashMap<i64, MyTask>>) {
+            self.tasks = tasks;
+        }
+
+        fn get_sync_base(&self) -> Option<SyncBase> {
+            self.sync_base.clone()
+        }
+
+        fn set_sync_base(&mut self, sync_base: Option<SyncBase>) {
+            self.sync_base = sync_base;
+        }
+
+        fn passphrase(&self) -> Option<String> {
+            None
+        }
+
+        fn to_serializable(&self) -> SerializableState {
+            SerializableState {
+                tasks: self.tasks.as_ref().map(SerializableState::serialize_tasks),
+                sync_base: self.sync_base.clone(),
+                quarantine: self.quarantine.clone(),
+                ..Default::default()
+            }
+        }
+    }
+
+    fn task(id: i64) -> MyTask {
+        MyTask {
+            id,
+            action: "Read".to_string(),
+            count_per_day: 10.0,
+            unit: "pages".to_string(),
+            logs: vec![],
+            start: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            end: NaiveDate::from_ymd_opt(2026, 1, 31).unwrap(),
+            effective_dow: crate::schedule::ALL_WEEKDAYS.to_vec(),
+            daily_tasks: None,
+            name: None,
+            archive: false,
+        }
+    }
+
+    fn remote_task_ids(backend: &MemoryBackend) -> Vec<i64> {
+        let remote = read_remote(&backend.remote.borrow().content, None).unwrap();
+        remote.tasks.unwrap_or_default().iter().map(|t| t.id).collect()
+    }
+
+    #[tokio::test]
+    async fn push_keeps_a_task_quarantined_locally_and_unchanged_remotely() {
+        let backend = MemoryBackend::default();
+        let mut state = TestState {
+            tasks: Some(HashMap::from([(1, task(1)), (2, task(2))])),
+            ..Default::default()
+        };
+        push(&mut state, &backend).await.unwrap();
+
+        // Task 2 becomes unreadable on this device, while the sync target is unchanged
+        let raw = serde_json::to_value(crate::serializable_task::SerializableTask::from(&task(2)))
+            .unwrap();
+        state.tasks.as_mut().unwrap().remove(&2);
+        state.quarantine.push(QuarantinedTask {
+            raw,
+            error: "Invalid date".to_string(),
+        });
+
+        assert!(push(&mut state, &backend).await.is_err());
+        assert_eq!(remote_task_ids(&backend), vec![1, 2]);
+        assert_eq!(state.quarantine.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn push_to_an_empty_target_ignores_the_base_of_another_target() {
+        let backend = MemoryBackend::default();
+        let mut state = TestState {
+            tasks: Some(HashMap::from([(1, task(1))])),
+            sync_base: Some(SyncBase {
+                revision: "sha-of-the-old-gist".to_string(),
+                tasks: vec![],
+            }),
+            ..Default::default()
+        };
+
+        push(&mut state, &backend).await.unwrap();
+
+        assert_eq!(*backend.stores.borrow(), vec![None]);
+        assert_eq!(remote_task_ids(&backend), vec![1]);
+        assert_eq!(state.sync_base.unwrap().revision, "rev-1");
+    }
+}
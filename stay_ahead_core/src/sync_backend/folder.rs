@@ -0,0 +1,506 @@
+use super::super::envelope::open;
+use super::super::merge::merge_tasks;
+use super::super::serializable_task::SerializableTask;
+use super::super::{MyTask, SerializableState};
+use super::{RemoteContent, SyncBackend, SyncError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/*
+ * Struct Definition
+ */
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct FolderConfig {
+    #[serde(default)]
+    pub folder: Option<String>,
+
+    #[serde(default)]
+    pub device_name: Option<String>,
+}
+
+// The other devices' files are combined before merging, so they are opened here.
+// `base_revision` is the revision of the last sync, what they are combined against.
+#[derive(Debug, Clone)]
+pub struct FolderSync {
+    pub config: FolderConfig,
+    pub passphrase: Option<String>,
+    pub base_revision: Option<String>,
+}
+
+// The other devices' files by name, as read at `revision`
+#[derive(Debug, Serialize, Deserialize)]
+struct SeenFiles {
+    revision: String,
+    files: HashMap<String, String>,
+}
+
+/*
+ * Local folder kept in sync by an external tool (Syncthing, Dropbox, ...)
+ *
+ * Every device only ever writes its own `<device_name>.json`, so the file
+ * sync tool never has to resolve a conflict. What the other devices wrote is
+ * combined into one state when fetching, and the revision is a hash of
+ * their files so that a change on any other device is noticed.
+ *
+ * Each file is combined against what it held at the last sync, kept in
+ * `.<device_name>.seen` next to this device's file. That way an entry removed
+ * on one device is not brought back by another device whose file still has it
+ * from before, and a device that has not synced for a while does not remove
+ * what was added elsewhere since. Without it, the files are combined as they
+ * are, as on the first sync.
+ *
+ * When two other devices changed a task in ways that cannot be combined, the
+ * version in the file whose name sorts first is kept, so every device
+ * settles on the same one. The other device is then shown the conflict when
+ * it merges, like any conflict with the sync target.
+ */
+
+impl SyncBackend for FolderSync {
+    fn is_configured(&self) -> bool {
+        [&self.config.folder, &self.config.device_name]
+            .iter()
+            .all(|field| field.as_ref().is_some_and(|value| !value.is_empty()))
+    }
+
+    async fn fetch(&self) -> Result<RemoteContent, String> {
+        let other_files = self.config.read_other_device_files()?;
+        let revision = revision_of(&other_files);
+
+        let seen = self.config.read_seen(self.base_revision.as_deref());
+        if let Err(e) = self.config.write_seen(&revision, &other_files) {
+            log::warn!("{}", e);
+        }
+
+        if other_files.is_empty() {
+            return Ok(RemoteContent {
+                content: String::new(),
+                revision,
+            });
+        }
+
+        // What the files still there held at the last sync, each file's changes since go on top
+        let bases: HashMap<&String, HashMap<i64, MyTask>> = other_files
+            .iter()
+            .filter_map(|(file_name, _)| {
+                let content = seen.get(file_name)?;
+                Some((file_name, self.device_tasks(file_name, content).ok()??))
+            })
+            .collect();
+
+        let mut combined: HashMap<i64, MyTask> = HashMap::new();
+        for base in bases.values() {
+            combined = merge_tasks(&HashMap::new(), &combined, base).tasks;
+        }
+
+        for (file_name, content) in &other_files {
+            let Some(tasks) = self.device_tasks(file_name, content)? else {
+                continue;
+            };
+
+            let base = bases.get(file_name).cloned().unwrap_or_default();
+            let outcome = merge_tasks(&base, &combined, &tasks);
+
+            for conflict in &outcome.conflicts {
+                log::warn!(
+                    "Task {} in {} conflicts with an earlier file in the sync folder, keeping the earlier one",
+                    conflict.task_id,
+                    file_name
+                );
+            }
+
+            combined = outcome.tasks;
+        }
+
+        let tasks: Vec<SerializableTask> = combined.values().map(SerializableTask::from).collect();
+        let content = serde_json::to_string(&serde_json::json!({ "tasks": tasks }))
+            .map_err(|e| format!("Serialization failed: {}", e))?;
+
+        Ok(RemoteContent { content, revision })
+    }
+
+    async fn store(
+        &self,
+        content: String,
+        _expected_revision: Option<String>,
+    ) -> Result<String, SyncError> {
+        let own_file = self.config.own_file()?;
+        let temp_file = own_file.with_extension("json.tmp");
+
+        std::fs::write(&temp_file, content)
+            .map_err(|e| format!("Failed to write {}: {}", temp_file.display(), e))?;
+        std::fs::rename(&temp_file, &own_file)
+            .map_err(|e| format!("Failed to write {}: {}", own_file.display(), e))?;
+
+        Ok(self.revision().await?)
+    }
+
+    async fn revision(&self) -> Result<String, String> {
+        Ok(revision_of(&self.config.read_other_device_files()?))
+    }
+}
+
+impl FolderSync {
+    // `None` for a file that is not a state, which is skipped
+    fn device_tasks(
+        &self,
+        file_name: &str,
+        content: &str,
+    ) -> Result<Option<HashMap<i64, MyTask>>, String> {
+        let content = open(content, self.passphrase.as_deref())
+            .map_err(|e| format!("{} ({})", e, file_name))?;
+
+        let tasks = match SerializableState::from_json(&content) {
+            Ok(device_state) => device_state.tasks.unwrap_or_default(),
+            Err(e) => {
+                log::warn!("Skipping {} in the sync folder: {}", file_name, e);
+                return Ok(None);
+            }
+        };
+
+        tasks
+            .iter()
+            .map(|t| MyTask::try_from(t).map(|task| (task.id, task)))
+            .collect::<Result<HashMap<_, _>, _>>()
+            .map(Some)
+            .map_err(|e| format!("Failed to read {}: {}", file_name, e))
+    }
+}
+
+impl FolderConfig {
+    fn own_file(&self) -> Result<PathBuf, String> {
+        match (&self.folder, &self.device_name) {
+            (Some(folder), Some(device_name)) => {
+                Ok(PathBuf::from(folder).join(format!("{}.json", device_name)))
+            }
+            _ => Err("Incomplete configuration for the sync folder".to_string()),
+        }
+    }
+
+    fn read_other_device_files(&self) -> Result<Vec<(String, String)>, String> {
+        let own_file = self.own_file()?;
+        let folder = own_file.parent().unwrap_or(&own_file);
+
+        let entries = std::fs::read_dir(folder)
+            .map_err(|e| format!("Failed to read {}: {}", folder.display(), e))?;
+
+        let mut files = vec![];
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path == own_file || path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+
+            let file_name = entry.file_name().to_string_lossy().to_string();
+
+            // Sync tools leave temporary and half-written files around
+            match std::fs::read_to_string(&path) {
+                Ok(content) => files.push((file_name, content)),
+                Err(e) => log::warn!("Skipping {} in the sync folder: {}", file_name, e),
+            }
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
+
+    fn seen_file(&self) -> Result<PathBuf, String> {
+        let own_file = self.own_file()?;
+        let device_name = self.device_name.as_deref().unwrap_or_default();
+
+        Ok(own_file.with_file_name(format!(".{}.seen", device_name)))
+    }
+
+    // The other devices' files at `revision`, or nothing when what was kept is from another one
+    fn read_seen(&self, revision: Option<&str>) -> HashMap<String, String> {
+        self.seen_file()
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<SeenFiles>(&content).ok())
+            .filter(|seen| Some(seen.revision.as_str()) == revision)
+            .map(|seen| seen.files)
+            .unwrap_or_default()
+    }
+
+    fn write_seen(&self, revision: &str, files: &[(String, String)]) -> Result<(), String> {
+        let seen_file = self.seen_file()?;
+        let seen = SeenFiles {
+            revision: revision.to_string(),
+            files: files.iter().cloned().collect(),
+        };
+        let json =
+            serde_json::to_string(&seen).map_err(|e| format!("Serialization failed: {}", e))?;
+
+        std::fs::write(&seen_file, json)
+            .map_err(|e| format!("Failed to write {}: {}", seen_file.display(), e))
+    }
+}
+
+// Saved as part of the sync base, so it has to stay the same across builds of the app.
+// Lengths go in too, so that moving text from one file to the next changes it.
+fn revision_of(files: &[(String, String)]) -> String {
+    let mut hasher = Sha256::new();
+
+    for (file_name, content) in files {
+        for part in [file_name, content] {
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part.as_bytes());
+        }
+    }
+
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::read_remote;
+    use crate::LogEntry;
+    use chrono::NaiveDate;
+
+    fn device(folder: &tempfile::TempDir, device_name: &str) -> FolderSync {
+        FolderSync {
+            config: FolderConfig {
+                folder: Some(folder.path().to_string_lossy().to_string()),
+                device_name: Some(device_name.to_string()),
+            },
+            passphrase: None,
+            base_revision: None,
+        }
+    }
+
+    // A device that has merged what it fetched
+    async fn synced(mut device: FolderSync) -> FolderSync {
+        device.base_revision = Some(device.fetch().await.unwrap().revision);
+        device
+    }
+
+    fn task(id: i64, logged: &[(i64, f32)]) -> MyTask {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        MyTask {
+            id,
+            action: "Read".to_string(),
+            count_per_day: 10.0,
+            unit: "pages".to_string(),
+            logs: logged
+                .iter()
+                .map(|(log_id, amount)| LogEntry {
+                    id: *log_id,
+                    amount: *amount,
+                    timestamp: start.and_hms_opt(12, 0, 0).unwrap(),
+                    note: None,
+                })
+                .collect(),
+            start,
+            end: NaiveDate::from_ymd_opt(2026, 1, 31).unwrap(),
+            effective_dow: crate::schedule::ALL_WEEKDAYS.to_vec(),
+            daily_tasks: None,
+            name: None,
+            archive: false,
+        }
+    }
+
+    fn state_with(tasks: &[MyTask]) -> String {
+        let tasks: Vec<SerializableTask> = tasks.iter().map(SerializableTask::from).collect();
+        serde_json::json!({ "tasks": tasks }).to_string()
+    }
+
+    async fn fetched_tasks(device: &FolderSync) -> HashMap<i64, MyTask> {
+        let remote = device.fetch().await.unwrap();
+
+        read_remote(&remote.content, None)
+            .unwrap()
+            .tasks
+            .unwrap_or_default()
+            .iter()
+            .map(|t| MyTask::try_from(t).map(|task| (task.id, task)).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn each_device_writes_its_own_file_and_reads_the_others() {
+        let folder = tempfile::tempdir().unwrap();
+        let laptop = device(&folder, "laptop");
+        let phone = device(&folder, "phone");
+
+        assert_eq!(laptop.fetch().await.unwrap().content, "");
+
+        laptop
+            .store(state_with(&[task(1, &[(10, 5.0)])]), None)
+            .await
+            .unwrap();
+
+        assert!(folder.path().join("laptop.json").exists());
+        assert!(!folder.path().join("laptop.json.tmp").exists());
+        assert_eq!(
+            fetched_tasks(&phone).await,
+            HashMap::from([(1, task(1, &[(10, 5.0)]))])
+        );
+        assert_eq!(laptop.fetch().await.unwrap().content, "");
+    }
+
+    #[tokio::test]
+    async fn the_revision_only_changes_with_other_devices_files() {
+        let folder = tempfile::tempdir().unwrap();
+        let laptop = device(&folder, "laptop");
+        let phone = device(&folder, "phone");
+
+        let before = laptop.revision().await.unwrap();
+        laptop
+            .store(state_with(&[task(1, &[])]), None)
+            .await
+            .unwrap();
+        assert_eq!(laptop.revision().await.unwrap(), before);
+
+        phone
+            .store(state_with(&[task(2, &[])]), None)
+            .await
+            .unwrap();
+        let after = laptop.revision().await.unwrap();
+        assert_ne!(after, before);
+        assert_eq!(laptop.fetch().await.unwrap().revision, after);
+    }
+
+    #[tokio::test]
+    async fn logs_from_several_devices_are_combined() {
+        let folder = tempfile::tempdir().unwrap();
+        std::fs::write(
+            folder.path().join("laptop.json"),
+            state_with(&[task(1, &[(10, 5.0)])]),
+        )
+        .unwrap();
+        std::fs::write(
+            folder.path().join("phone.json"),
+            state_with(&[task(1, &[(20, 3.0)]), task(2, &[])]),
+        )
+        .unwrap();
+
+        let tasks = fetched_tasks(&device(&folder, "tablet")).await;
+
+        assert_eq!(tasks[&1].count_accum(), 8.0);
+        assert!(tasks.contains_key(&2));
+    }
+
+    #[tokio::test]
+    async fn a_conflict_between_devices_keeps_the_file_sorting_first() {
+        let folder = tempfile::tempdir().unwrap();
+        std::fs::write(
+            folder.path().join("phone.json"),
+            state_with(&[task(1, &[(10, 3.0)])]),
+        )
+        .unwrap();
+        std::fs::write(
+            folder.path().join("laptop.json"),
+            state_with(&[task(1, &[(10, 5.0)])]),
+        )
+        .unwrap();
+
+        // Every device reading both files settles on the same version
+        for device_name in ["desktop", "tablet"] {
+            let tasks = fetched_tasks(&device(&folder, device_name)).await;
+            assert_eq!(tasks[&1], task(1, &[(10, 5.0)]));
+        }
+    }
+
+    #[tokio::test]
+    async fn an_unreadable_device_file_is_skipped() {
+        let folder = tempfile::tempdir().unwrap();
+        std::fs::write(folder.path().join("broken.json"), "{ not json").unwrap();
+        std::fs::write(folder.path().join("notes.txt"), "not a device").unwrap();
+        std::fs::write(
+            folder.path().join("phone.json"),
+            state_with(&[task(1, &[])]),
+        )
+        .unwrap();
+
+        let tasks = fetched_tasks(&device(&folder, "laptop")).await;
+
+        assert_eq!(tasks.keys().collect::<Vec<_>>(), vec![&1]);
+    }
+
+    #[tokio::test]
+    async fn an_entry_removed_on_one_device_is_not_brought_back_by_another() {
+        let folder = tempfile::tempdir().unwrap();
+        let both = state_with(&[task(1, &[(10, 5.0), (20, 3.0)])]);
+        std::fs::write(folder.path().join("laptop.json"), &both).unwrap();
+        std::fs::write(folder.path().join("phone.json"), &both).unwrap();
+        let tablet = synced(device(&folder, "tablet")).await;
+
+        std::fs::write(
+            folder.path().join("laptop.json"),
+            state_with(&[task(1, &[(20, 3.0)])]),
+        )
+        .unwrap();
+
+        let tasks = fetched_tasks(&tablet).await;
+        assert_eq!(tasks[&1], task(1, &[(20, 3.0)]));
+
+        // Without what the files held at the last sync, they are combined as they are
+        let tasks = fetched_tasks(&device(&folder, "tablet")).await;
+        assert_eq!(tasks[&1], task(1, &[(10, 5.0), (20, 3.0)]));
+    }
+
+    #[tokio::test]
+    async fn a_device_that_has_not_synced_since_does_not_remove_what_was_added() {
+        let folder = tempfile::tempdir().unwrap();
+        std::fs::write(
+            folder.path().join("laptop.json"),
+            state_with(&[task(1, &[])]),
+        )
+        .unwrap();
+        std::fs::write(
+            folder.path().join("phone.json"),
+            state_with(&[task(1, &[]), task(2, &[])]),
+        )
+        .unwrap();
+        let tablet = synced(device(&folder, "tablet")).await;
+
+        std::fs::write(
+            folder.path().join("phone.json"),
+            state_with(&[task(1, &[]), task(2, &[(10, 5.0)])]),
+        )
+        .unwrap();
+
+        let tasks = fetched_tasks(&tablet).await;
+        assert_eq!(tasks[&2], task(2, &[(10, 5.0)]));
+    }
+
+    #[tokio::test]
+    async fn a_file_that_cannot_be_read_as_text_is_skipped() {
+        let folder = tempfile::tempdir().unwrap();
+        std::fs::write(folder.path().join("phone.sync-conflict.json"), [0xff, 0xfe]).unwrap();
+        std::fs::write(
+            folder.path().join("phone.json"),
+            state_with(&[task(1, &[])]),
+        )
+        .unwrap();
+
+        let tasks = fetched_tasks(&device(&folder, "laptop")).await;
+
+        assert_eq!(tasks.keys().collect::<Vec<_>>(), vec![&1]);
+    }
+
+    #[test]
+    fn the_revision_is_a_sha256_of_the_files() {
+        assert_eq!(
+            revision_of(&[]),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_ne!(
+            revision_of(&[("a.json".to_string(), "bc".to_string())]),
+            revision_of(&[
+                ("a.json".to_string(), "b".to_string()),
+                ("c".to_string(), String::new())
+            ])
+        );
+    }
+}
@@ -0,0 +1,770 @@
+use super::super::envelope::is_sealed;
+use super::{RemoteContent, SyncBackend, SyncError};
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+const GITHUB_API: &str = "https://api.github.com";
+
+// Set on the gists created here, so they are easy to recognise on github.com
+const GIST_DESCRIPTION: &str = "Stay Ahead sync data";
+
+const DEFAULT_FILE_NAME: &str = "StayAhead.json";
+
+/*
+ * Struct Definition
+ */
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct GistConfig {
+    #[serde(default)]
+    pub github_pat: Option<String>,
+
+    #[serde(default)]
+    pub gist_id: Option<String>,
+
+    #[serde(default)]
+    pub gist_file_name: Option<String>,
+
+    // Only set for GitHub Enterprise, or to point the client at a mock server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+}
+
+// One entry of the gist's history, newest first
+#[derive(Debug, Clone, PartialEq)]
+pub struct GistRevision {
+    pub version: String,
+    pub committed_at: String,
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+// A gist of the user that holds a state file
+#[derive(Debug, Clone, PartialEq)]
+pub struct GistCandidate {
+    pub gist_id: String,
+    pub file_name: String,
+    pub description: String,
+    pub updated_at: String,
+}
+
+/*
+ * GitHub Gist as a sync backend
+ */
+
+impl SyncBackend for GistConfig {
+    fn is_configured(&self) -> bool {
+        [&self.github_pat, &self.gist_id, &self.gist_file_name]
+            .iter()
+            .all(|field| field.as_ref().is_some_and(|value| !value.is_empty()))
+    }
+
+    async fn fetch(&self) -> Result<RemoteContent, String> {
+        pull_from_gist(self, None).await.map_err(|e| e.to_string())
+    }
+
+    // Gists have no conditional update, so the revision is checked right before writing.
+    // Only a push from another device landing in between can still be overwritten.
+    async fn store(
+        &self,
+        content: String,
+        expected_revision: Option<String>,
+    ) -> Result<String, SyncError> {
+        if let Some(expected_revision) = expected_revision.filter(|r| !r.is_empty()) {
+            if self.revision().await? != expected_revision {
+                return Err("The gist was changed by another device. Please try again."
+                    .to_string()
+                    .into());
+            }
+        }
+
+        push_to_gist(self, content).await
+    }
+
+    async fn revision(&self) -> Result<String, String> {
+        let (github_pat, gist_id, _) = self.credentials()?;
+
+        let client = reqwest::Client::new();
+        let get_url = format!("{}/gists/{}/commits?per_page=1", self.api_url(), gist_id);
+
+        let response = client
+            .get(&get_url)
+            .header("Authorization", format!("Bearer {}", github_pat))
+            .header("User-Agent", "Stay Ahead - Palegg Works")
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request: {e}"))?;
+
+        if !response.status().is_success() {
+            return Err(format!("GitHub API request failed: {}", response.status()));
+        }
+
+        let commits: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to read response: {e}"))?;
+
+        // Every gist has at least the version it was created with
+        commits[0]["version"]
+            .as_str()
+            .filter(|version| !version.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| "GitHub returned no revision of the gist".to_string())
+    }
+}
+
+impl GistConfig {
+    fn api_url(&self) -> &str {
+        self.api_url
+            .as_deref()
+            .filter(|url| !url.is_empty())
+            .unwrap_or(GITHUB_API)
+            .trim_end_matches('/')
+    }
+
+    fn credentials(&self) -> Result<(String, String, String), String> {
+        match (&self.github_pat, &self.gist_id, &self.gist_file_name) {
+            (Some(github_pat), Some(gist_id), Some(gist_file_name)) => Ok((
+                github_pat.clone(),
+                gist_id.clone(),
+                gist_file_name.clone(),
+            )),
+            _ => Err("Incomplete configuration for GitHub Gist".to_string()),
+        }
+    }
+}
+
+// The latest version, or the one at `revision` when given
+async fn pull_from_gist(
+    config: &GistConfig,
+    revision: Option<&str>,
+) -> Result<RemoteContent, Box<dyn std::error::Error + 'static>> {
+    let (github_pat, gist_id, gist_file_name) = config.credentials()?;
+
+    let client = reqwest::Client::new();
+    let get_url = match revision {
+        Some(revision) => format!("{}/gists/{}/{}", config.api_url(), gist_id, revision),
+        None => format!("{}/gists/{}", config.api_url(), gist_id),
+    };
+
+    let response = client
+        .get(&get_url)
+        .header("Authorization", format!("Bearer {}", github_pat))
+        .header("User-Agent", "Stay Ahead - Palegg Works")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(format!("GitHub API request failed: {}", response.status()).into());
+    }
+
+    let gist: serde_json::Value = response.json().await?;
+
+    let content = gist["files"][gist_file_name]["content"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+
+    // The first history item is the revision we just read
+    let revision = match revision {
+        Some(revision) => revision.to_string(),
+        None => gist["history"][0]["version"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    };
+
+    Ok(RemoteContent { content, revision })
+}
+
+async fn push_to_gist(config: &GistConfig, new_content: String) -> Result<String, SyncError> {
+    let (github_pat, gist_id, gist_file_name) = config.credentials()?;
+
+    let client = reqwest::Client::new();
+    let get_url = format!("{}/gists/{}", config.api_url(), gist_id);
+
+    let mut files = serde_json::Map::new();
+    files.insert(
+        gist_file_name.to_string(),
+        serde_json::json!({ "content": new_content }),
+    );
+
+    let patch_body = serde_json::json!({ "files": files });
+
+    let response = client
+        .patch(&get_url)
+        .header("Authorization", format!("Bearer {}", github_pat))
+        .header("User-Agent", "Stay Ahead - Palegg Works")
+        .json(&patch_body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request: {e}"))?;
+
+    if response.status().is_success() {
+        let gist: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to read response: {e}"))?;
+
+        let revision = gist["history"][0]["version"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+
+        Ok(revision)
+    } else {
+        let reset_time = response
+            .headers()
+            .get("x-ratelimit-reset")
+            .and_then(|val| val.to_str().ok())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        // The reset time is sent on every response, but only matters once the limit is used up
+        let rate_limited = response
+            .headers()
+            .get("x-ratelimit-remaining")
+            .is_some_and(|val| val == "0");
+
+        let retry_at = reset_time
+            .parse::<i64>()
+            .ok()
+            .filter(|_| rate_limited)
+            .and_then(|secs| DateTime::from_timestamp(secs, 0));
+
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+
+        Err(SyncError {
+            message: format!(
+                "Push error: {status} - {body}\n⚠️ Retry after: x-ratelimit-reset = {reset_time}"
+            ),
+            retry_at,
+        })
+    }
+}
+
+/*
+ * Every push is a revision of the gist, which makes it a history of the state
+ */
+
+impl GistConfig {
+    pub async fn revisions(&self) -> Result<Vec<GistRevision>, String> {
+        let (github_pat, gist_id, _) = self.credentials()?;
+
+        let client = reqwest::Client::new();
+        let get_url = format!("{}/gists/{}/commits?per_page=100", self.api_url(), gist_id);
+        let commits = get_all_pages(&client, &github_pat, get_url).await?;
+
+        Ok(commits
+            .iter()
+            .map(|commit| GistRevision {
+                version: commit["version"].as_str().unwrap_or_default().to_string(),
+                committed_at: commit["committed_at"].as_str().unwrap_or_default().to_string(),
+                additions: commit["change_status"]["additions"].as_u64().unwrap_or_default(),
+                deletions: commit["change_status"]["deletions"].as_u64().unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    pub async fn fetch_revision(&self, revision: &str) -> Result<RemoteContent, String> {
+        pull_from_gist(self, Some(revision))
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/*
+ * Setting up sync with only a PAT
+ */
+
+impl GistConfig {
+    // Returns the config for the new secret gist and the revision of its first version
+    pub async fn create_gist(&self, content: String) -> Result<(GistConfig, String), String> {
+        let github_pat = self
+            .github_pat
+            .clone()
+            .filter(|pat| !pat.is_empty())
+            .ok_or_else(|| "Enter a GitHub PAT first".to_string())?;
+
+        let gist_file_name = self
+            .gist_file_name
+            .clone()
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| DEFAULT_FILE_NAME.to_string());
+
+        let mut files = serde_json::Map::new();
+        files.insert(
+            gist_file_name.clone(),
+            serde_json::json!({ "content": content }),
+        );
+
+        let post_body = serde_json::json!({
+            "description": GIST_DESCRIPTION,
+            "public": false,
+            "files": files,
+        });
+
+        let client = reqwest::Client::new();
+        let post_url = format!("{}/gists", self.api_url());
+
+        let response = client
+            .post(&post_url)
+            .header("Authorization", format!("Bearer {}", github_pat))
+            .header("User-Agent", "Stay Ahead - Palegg Works")
+            .json(&post_body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request: {e}"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Failed to create the gist: {status} - {body}"));
+        }
+
+        let gist: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to read response: {e}"))?;
+
+        let gist_id = gist["id"]
+            .as_str()
+            .ok_or_else(|| "GitHub did not return the ID of the new gist".to_string())?
+            .to_string();
+
+        let revision = gist["history"][0]["version"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+
+        let config = GistConfig {
+            github_pat: Some(github_pat),
+            gist_id: Some(gist_id),
+            gist_file_name: Some(gist_file_name),
+            api_url: self.api_url.clone(),
+        };
+
+        Ok((config, revision))
+    }
+
+    // The listing has no file contents, so every gist with a JSON file is looked into
+    pub async fn find_gists(&self) -> Result<Vec<GistCandidate>, String> {
+        let github_pat = self
+            .github_pat
+            .clone()
+            .filter(|pat| !pat.is_empty())
+            .ok_or_else(|| "Enter a GitHub PAT first".to_string())?;
+
+        let client = reqwest::Client::new();
+        let get_url = format!("{}/gists?per_page=100", self.api_url());
+        let gists = get_all_pages(&client, &github_pat, get_url).await?;
+
+        let mut candidates = vec![];
+
+        for gist in gists {
+            let has_json_file = gist["files"]
+                .as_object()
+                .is_some_and(|files| files.keys().any(|name| name.ends_with(".json")));
+
+            if !has_json_file {
+                continue;
+            }
+
+            let gist_id = gist["id"].as_str().unwrap_or_default().to_string();
+            let get_url = format!("{}/gists/{}", self.api_url(), gist_id);
+
+            // One gist that cannot be read should not hide the others
+            let gist = match get_json(&client, &github_pat, &get_url).await {
+                Ok(gist) => gist,
+                Err(e) => {
+                    log::warn!("Skipping gist {}: {}", gist_id, e);
+                    continue;
+                }
+            };
+
+            let Some(files) = gist["files"].as_object() else {
+                continue;
+            };
+
+            for (file_name, file) in files {
+                let content = file["content"].as_str().unwrap_or_default();
+
+                if file_name.ends_with(".json") && is_state_file(content) {
+                    candidates.push(GistCandidate {
+                        gist_id: gist_id.clone(),
+                        file_name: file_name.clone(),
+                        description: gist["description"].as_str().unwrap_or_default().to_string(),
+                        updated_at: gist["updated_at"].as_str().unwrap_or_default().to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(candidates)
+    }
+}
+
+async fn get_json(
+    client: &reqwest::Client,
+    github_pat: &str,
+    url: &str,
+) -> Result<serde_json::Value, String> {
+    let response = client
+        .get(url)
+        .header("Authorization", format!("Bearer {}", github_pat))
+        .header("User-Agent", "Stay Ahead - Palegg Works")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request: {e}"))?;
+
+    if !response.status().is_success() {
+        return Err(format!("GitHub API request failed: {}", response.status()));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to read response: {e}"))
+}
+
+// GitHub lists at most 100 items at a time, and links to the next page in the `Link` header
+async fn get_all_pages(
+    client: &reqwest::Client,
+    github_pat: &str,
+    url: String,
+) -> Result<Vec<serde_json::Value>, String> {
+    let mut items = vec![];
+    let mut next_url = Some(url);
+
+    while let Some(url) = next_url {
+        let response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", github_pat))
+            .header("User-Agent", "Stay Ahead - Palegg Works")
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request: {e}"))?;
+
+        if !response.status().is_success() {
+            return Err(format!("GitHub API request failed: {}", response.status()));
+        }
+
+        next_url = next_page(&response);
+
+        let page: Vec<serde_json::Value> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to read response: {e}"))?;
+
+        items.extend(page);
+    }
+
+    Ok(items)
+}
+
+// `<https://api.github.com/gists?page=2>; rel="next", <...>; rel="last"`
+fn next_page(response: &reqwest::Response) -> Option<String> {
+    let link = response.headers().get("Link")?.to_str().ok()?;
+
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == r#"rel="next""#)
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
+// Either an encrypted payload, or a state with a task list
+fn is_state_file(content: &str) -> bool {
+    if is_sealed(content) {
+        return true;
+    }
+
+    serde_json::from_str::<serde_json::Value>(content)
+        .is_ok_and(|value| value.get("tasks").is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn config(server: &MockServer) -> GistConfig {
+        GistConfig {
+            github_pat: Some("ghp_test".to_string()),
+            api_url: Some(server.uri()),
+            ..Default::default()
+        }
+    }
+
+    fn listed_gist(id: &str, file_name: &str) -> serde_json::Value {
+        serde_json::json!({ "id": id, "files": { file_name: {} } })
+    }
+
+    fn full_gist(id: &str, file_name: &str, content: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "description": "Stay Ahead sync data",
+            "updated_at": "2026-03-01T12:00:00Z",
+            "files": { file_name: { "content": content } },
+        })
+    }
+
+    #[tokio::test]
+    async fn create_gist_makes_a_secret_gist() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/gists"))
+            .and(header("Authorization", "Bearer ghp_test"))
+            .and(body_partial_json(serde_json::json!({
+                "description": GIST_DESCRIPTION,
+                "public": false,
+                "files": { DEFAULT_FILE_NAME: { "content": "{\"tasks\":[]}" } },
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "aa5a315d61ae9438b18d",
+                "history": [{ "version": "57a7f021a713b1c5a6a199b54cc514735d2d462f" }],
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (created, revision) = config(&server)
+            .create_gist("{\"tasks\":[]}".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(created.gist_id.as_deref(), Some("aa5a315d61ae9438b18d"));
+        assert_eq!(created.gist_file_name.as_deref(), Some(DEFAULT_FILE_NAME));
+        assert_eq!(created.api_url, Some(server.uri()));
+        assert_eq!(revision, "57a7f021a713b1c5a6a199b54cc514735d2d462f");
+    }
+
+    fn synced_gist(server: &MockServer) -> GistConfig {
+        GistConfig {
+            gist_id: Some("aa5a315d61ae9438b18d".to_string()),
+            gist_file_name: Some(DEFAULT_FILE_NAME.to_string()),
+            ..config(server)
+        }
+    }
+
+    async fn mount_latest_revision(server: &MockServer, version: &str) {
+        Mock::given(method("GET"))
+            .and(path("/gists/aa5a315d61ae9438b18d/commits"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([{ "version": version }])),
+            )
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn a_push_goes_ahead_when_the_gist_is_at_the_expected_revision() {
+        let server = MockServer::start().await;
+        mount_latest_revision(&server, "v1").await;
+        Mock::given(method("PATCH"))
+            .and(path("/gists/aa5a315d61ae9438b18d"))
+            .and(body_partial_json(serde_json::json!({
+                "files": { DEFAULT_FILE_NAME: { "content": "{}" } },
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "history": [{ "version": "v2" }],
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let revision = synced_gist(&server)
+            .store("{}".to_string(), Some("v1".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(revision, "v2");
+    }
+
+    #[tokio::test]
+    async fn a_push_after_another_device_is_refused() {
+        let server = MockServer::start().await;
+        mount_latest_revision(&server, "v2").await;
+        Mock::given(method("PATCH"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let error = synced_gist(&server)
+            .store("{}".to_string(), Some("v1".to_string()))
+            .await
+            .unwrap_err();
+
+        assert!(error.message.contains("changed by another device"));
+    }
+
+    #[tokio::test]
+    async fn a_gist_without_history_has_no_revision() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/gists/aa5a315d61ae9438b18d/commits"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&server)
+            .await;
+
+        assert!(synced_gist(&server).revision().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn create_gist_needs_a_pat() {
+        assert!(GistConfig::default()
+            .create_gist(String::new())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn find_gists_reads_every_page_and_skips_gists_that_fail() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/gists"))
+            .and(query_param("page", "2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([listed_gist("second", "data.json")])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/gists"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "Link",
+                        format!(
+                            r#"<{0}/gists?per_page=100&page=2>; rel="next", <{0}/gists?per_page=100&page=2>; rel="last""#,
+                            server.uri()
+                        )
+                        .as_str(),
+                    )
+                    .set_body_json(serde_json::json!([
+                        listed_gist("first", "StayAhead.json"),
+                        listed_gist("broken", "StayAhead.json"),
+                        listed_gist("notes", "notes.md"),
+                        listed_gist("other", "package.json"),
+                    ])),
+            )
+            .mount(&server)
+            .await;
+
+        for (id, file_name, content) in [
+            ("first", "StayAhead.json", r#"{"tasks":[]}"#),
+            ("other", "package.json", r#"{"name":"something else"}"#),
+            ("second", "data.json", r#"{"stay_ahead_envelope":1}"#),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("/gists/{}", id)))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(full_gist(id, file_name, content)),
+                )
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/gists/broken"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let found = config(&server).find_gists().await.unwrap();
+
+        assert_eq!(
+            found,
+            vec![
+                GistCandidate {
+                    gist_id: "first".to_string(),
+                    file_name: "StayAhead.json".to_string(),
+                    description: "Stay Ahead sync data".to_string(),
+                    updated_at: "2026-03-01T12:00:00Z".to_string(),
+                },
+                GistCandidate {
+                    gist_id: "second".to_string(),
+                    file_name: "data.json".to_string(),
+                    description: "Stay Ahead sync data".to_string(),
+                    updated_at: "2026-03-01T12:00:00Z".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn revisions_are_read_from_every_page() {
+        let server = MockServer::start().await;
+        let commit = |version: &str| {
+            serde_json::json!({
+                "version": version,
+                "committed_at": "2026-03-01T12:00:00Z",
+                "change_status": { "additions": 2, "deletions": 1 },
+            })
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/gists/aa5a315d61ae9438b18d/commits"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![commit("oldest")]))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/gists/aa5a315d61ae9438b18d/commits"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "Link",
+                        format!(
+                            r#"<{}/gists/aa5a315d61ae9438b18d/commits?per_page=100&page=2>; rel="next""#,
+                            server.uri()
+                        )
+                        .as_str(),
+                    )
+                    .set_body_json(vec![commit("newest"), commit("middle")]),
+            )
+            .mount(&server)
+            .await;
+
+        let config = GistConfig {
+            gist_id: Some("aa5a315d61ae9438b18d".to_string()),
+            gist_file_name: Some(DEFAULT_FILE_NAME.to_string()),
+            ..config(&server)
+        };
+        let revisions = config.revisions().await.unwrap();
+
+        assert_eq!(
+            revisions
+                .iter()
+                .map(|r| r.version.as_str())
+                .collect::<Vec<_>>(),
+            vec!["newest", "middle", "oldest"]
+        );
+        assert_eq!(revisions[2].additions, 2);
+        assert_eq!(revisions[2].deletions, 1);
+    }
+
+    #[tokio::test]
+    async fn find_gists_fails_when_the_listing_fails() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/gists"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        assert!(config(&server).find_gists().await.is_err());
+    }
+}
@@ -0,0 +1,144 @@
+mod folder;
+mod gist;
+mod s3;
+mod webdav;
+
+pub use folder::{FolderConfig, FolderSync};
+pub use gist::{GistCandidate, GistConfig, GistRevision};
+pub use s3::S3Config;
+pub use webdav::WebDavConfig;
+
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+/*
+ * Struct Definition
+ */
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, EnumIter)]
+pub enum SyncBackendKind {
+    #[default]
+    Gist,
+    WebDav,
+    S3,
+    Folder,
+}
+
+impl std::fmt::Display for SyncBackendKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SyncBackendKind::Gist => write!(f, "GitHub Gist"),
+            SyncBackendKind::WebDav => write!(f, "WebDAV (e.g. Nextcloud)"),
+            SyncBackendKind::S3 => write!(f, "S3-compatible storage (e.g. MinIO)"),
+            SyncBackendKind::Folder => write!(f, "Local folder (e.g. Syncthing, Dropbox)"),
+        }
+    }
+}
+
+impl SyncBackendKind {
+    // Picking a folder is only possible on desktop
+    pub fn is_available(&self) -> bool {
+        match self {
+            SyncBackendKind::Folder => {
+                cfg!(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))
+            }
+            _ => true,
+        }
+    }
+}
+
+// The state file as found on the sync target, along with the revision it was read at
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RemoteContent {
+    pub content: String,
+    pub revision: String,
+}
+
+// Why a write failed, and when the sync target allows the next attempt if it said so
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncError {
+    pub message: String,
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+impl From<String> for SyncError {
+    fn from(message: String) -> Self {
+        SyncError {
+            message,
+            retry_at: None,
+        }
+    }
+}
+
+impl std::fmt::Display for SyncError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+// When a busy or rate-limiting server asked to try again, from its `Retry-After` header
+fn retry_after(response: &reqwest::Response) -> Option<DateTime<Utc>> {
+    if response.status() != StatusCode::TOO_MANY_REQUESTS
+        && response.status() != StatusCode::SERVICE_UNAVAILABLE
+    {
+        return None;
+    }
+
+    let value = response.headers().get("Retry-After")?.to_str().ok()?;
+    parse_retry_after(value, Utc::now())
+}
+
+// Either a number of seconds or an HTTP date
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+
+    match value.parse::<u32>() {
+        Ok(seconds) => Some(now + chrono::Duration::seconds(seconds.into())),
+        Err(_) => DateTime::parse_from_rfc2822(value)
+            .ok()
+            .map(|date| date.with_timezone(&Utc)),
+    }
+}
+
+/*
+ * Trait implemented by every place the state can be synced to
+ */
+
+#[allow(async_fn_in_trait)]
+pub trait SyncBackend {
+    fn is_configured(&self) -> bool;
+
+    // Empty content means nothing has been stored yet
+    async fn fetch(&self) -> Result<RemoteContent, String>;
+
+    // Backends that support it reject the write if the target is no longer at `expected_revision`.
+    // Returns the revision of what was written.
+    async fn store(&self, content: String, expected_revision: Option<String>)
+        -> Result<String, SyncError>;
+
+    async fn revision(&self) -> Result<String, String>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn retry_after_is_read_as_seconds_or_as_a_date() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Utc.with_ymd_and_hms(2026, 3, 1, 12, 2, 0).unwrap())
+        );
+        assert_eq!(
+            parse_retry_after("Sun, 01 Mar 2026 13:00:00 GMT", now),
+            Some(Utc.with_ymd_and_hms(2026, 3, 1, 13, 0, 0).unwrap())
+        );
+        assert_eq!(parse_retry_after("-5", now), None);
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+}
@@ -0,0 +1,284 @@
+use super::{retry_after, RemoteContent, SyncBackend, SyncError};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+/*
+ * Struct Definition
+ */
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct WebDavConfig {
+    #[serde(default)]
+    pub url: Option<String>,
+
+    #[serde(default)]
+    pub username: Option<String>,
+
+    #[serde(default)]
+    pub password: Option<String>,
+
+    #[serde(default)]
+    pub file_path: Option<String>,
+}
+
+/*
+ * WebDAV server (e.g. Nextcloud) as a sync backend
+ *
+ * The ETag of the state file is used as its revision, and writes are
+ * conditional on it so that a concurrent write from another device is
+ * rejected instead of overwritten.
+ */
+
+impl SyncBackend for WebDavConfig {
+    fn is_configured(&self) -> bool {
+        [&self.url, &self.username, &self.password, &self.file_path]
+            .iter()
+            .all(|field| field.as_ref().is_some_and(|value| !value.is_empty()))
+    }
+
+    async fn fetch(&self) -> Result<RemoteContent, String> {
+        let response = self
+            .request(reqwest::Method::GET)?
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request: {e}"))?;
+
+        // Nothing has been pushed yet
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(RemoteContent {
+                content: String::new(),
+                revision: String::new(),
+            });
+        }
+
+        if !response.status().is_success() {
+            return Err(format!("WebDAV request failed: {}", response.status()));
+        }
+
+        let revision = etag(&response);
+        let content = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read response: {e}"))?;
+
+        Ok(RemoteContent { content, revision })
+    }
+
+    async fn store(
+        &self,
+        content: String,
+        expected_revision: Option<String>,
+    ) -> Result<String, SyncError> {
+        let request = self.request(reqwest::Method::PUT)?;
+
+        let request = match expected_revision.filter(|revision| !revision.is_empty()) {
+            Some(revision) => request.header("If-Match", revision),
+            None => request.header("If-None-Match", "*"),
+        };
+
+        let response = request
+            .header("Content-Type", "application/json")
+            .body(content)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request: {e}"))?;
+
+        if response.status() == StatusCode::PRECONDITION_FAILED {
+            return Err(
+                "The file on the WebDAV server was changed by another device. Please try again."
+                    .to_string()
+                    .into(),
+            );
+        }
+
+        if !response.status().is_success() {
+            let retry_at = retry_after(&response);
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(SyncError {
+                message: format!("Push error: {status} - {body}"),
+                retry_at,
+            });
+        }
+
+        // Not every server sends the new ETag back on PUT
+        let revision = etag(&response);
+        if revision.is_empty() {
+            Ok(self.revision().await?)
+        } else {
+            Ok(revision)
+        }
+    }
+
+    async fn revision(&self) -> Result<String, String> {
+        let response = self
+            .request(reqwest::Method::HEAD)?
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request: {e}"))?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(String::new());
+        }
+
+        if !response.status().is_success() {
+            return Err(format!("WebDAV request failed: {}", response.status()));
+        }
+
+        Ok(etag(&response))
+    }
+}
+
+impl WebDavConfig {
+    fn request(&self, method: reqwest::Method) -> Result<reqwest::RequestBuilder, String> {
+        match (&self.url, &self.username, &self.password, &self.file_path) {
+            (Some(url), Some(username), Some(password), Some(file_path)) => {
+                let file_url = format!(
+                    "{}/{}",
+                    url.trim_end_matches('/'),
+                    file_path.trim_start_matches('/')
+                );
+
+                Ok(reqwest::Client::new()
+                    .request(method, file_url)
+                    .basic_auth(username, Some(password))
+                    .header("User-Agent", "Stay Ahead - Palegg Works"))
+            }
+            _ => Err("Incomplete configuration for WebDAV".to_string()),
+        }
+    }
+}
+
+fn etag(response: &reqwest::Response) -> String {
+    response
+        .headers()
+        .get("ETag")
+        .and_then(|val| val.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_string, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn config(server: &MockServer) -> WebDavConfig {
+        WebDavConfig {
+            url: Some(server.uri()),
+            username: Some("me".to_string()),
+            password: Some("app-password".to_string()),
+            file_path: Some("StayAhead.json".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn a_push_only_replaces_the_revision_it_was_based_on() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/StayAhead.json"))
+            .and(header("If-Match", "\"etag-1\""))
+            .and(body_string("{}"))
+            .respond_with(ResponseTemplate::new(204).insert_header("ETag", "\"etag-2\""))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let revision = config(&server)
+            .store("{}".to_string(), Some("\"etag-1\"".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(revision, "\"etag-2\"");
+    }
+
+    #[tokio::test]
+    async fn a_first_push_does_not_replace_a_file_pushed_meanwhile() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(header("If-None-Match", "*"))
+            .respond_with(ResponseTemplate::new(201).insert_header("ETag", "\"etag-1\""))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let revision = config(&server)
+            .store("{}".to_string(), Some(String::new()))
+            .await
+            .unwrap();
+
+        assert_eq!(revision, "\"etag-1\"");
+    }
+
+    #[tokio::test]
+    async fn a_push_after_another_device_is_refused() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(412))
+            .mount(&server)
+            .await;
+
+        let error = config(&server)
+            .store("{}".to_string(), Some("\"etag-1\"".to_string()))
+            .await
+            .unwrap_err();
+
+        assert!(error.message.contains("changed by another device"));
+        assert_eq!(error.retry_at, None);
+    }
+
+    #[tokio::test]
+    async fn the_revision_is_asked_for_when_a_push_returns_none() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(200).insert_header("ETag", "\"etag-2\""))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let revision = config(&server)
+            .store("{}".to_string(), Some("\"etag-1\"".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(revision, "\"etag-2\"");
+    }
+
+    #[tokio::test]
+    async fn a_rate_limited_push_says_when_to_retry() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "120"))
+            .mount(&server)
+            .await;
+
+        let error = config(&server)
+            .store(String::new(), None)
+            .await
+            .unwrap_err();
+
+        let wait = error.retry_at.unwrap() - chrono::Utc::now();
+        assert!(wait > chrono::Duration::seconds(110) && wait <= chrono::Duration::seconds(120));
+    }
+
+    #[tokio::test]
+    async fn other_failures_leave_the_retry_to_the_backoff() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(500).insert_header("Retry-After", "120"))
+            .mount(&server)
+            .await;
+
+        let error = config(&server)
+            .store(String::new(), None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.retry_at, None);
+    }
+}
@@ -0,0 +1,123 @@
+use super::load_error::TaskError;
+use super::log_entry::LogEntry;
+use super::serializable_task::SerializableTask;
+use chrono::{NaiveDate, Weekday};
+use std::collections::HashMap;
+
+/*
+ * Struct Definition
+ */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MyTask {
+    pub id: i64,
+    pub action: String,
+    pub count_per_day: f32,
+    pub unit: String,
+    pub logs: Vec<LogEntry>,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub effective_dow: Vec<Weekday>,
+    pub daily_tasks: Option<Vec<String>>,
+    pub name: Option<String>,
+    pub archive: bool,
+}
+
+impl MyTask {
+    // Its name, or what it asks for each day when it has none
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("{} {} {}", self.action, self.count_per_day, self.unit),
+        }
+    }
+
+    // Folded from 0.0, since an empty float sum is -0.0 and would show as "-0.0"
+    pub fn count_accum(&self) -> f32 {
+        self.logs.iter().fold(0.0, |sum, entry| sum + entry.amount)
+    }
+
+    pub fn logged_per_day(&self) -> HashMap<NaiveDate, f32> {
+        let mut per_day = HashMap::new();
+        for entry in &self.logs {
+            *per_day.entry(entry.timestamp.date()).or_insert(0.0) += entry.amount;
+        }
+        per_day
+    }
+
+    pub fn remove_log(&mut self, log_id: i64) -> Option<LogEntry> {
+        let index = self.logs.iter().position(|entry| entry.id == log_id)?;
+        Some(self.logs.remove(index))
+    }
+
+    // The most recent submission is the one created last, not the one with the latest date
+    pub fn undo_last_log(&mut self) -> Option<LogEntry> {
+        let last_id = self.logs.iter().map(|entry| entry.id).max()?;
+        self.remove_log(last_id)
+    }
+}
+
+/*
+ * Conversion from SerializableTask to MyTask
+ */
+
+impl TryFrom<&SerializableTask> for MyTask {
+    type Error = TaskError;
+
+    fn try_from(task: &SerializableTask) -> Result<Self, Self::Error> {
+        let start = NaiveDate::parse_from_str(&task.start, "%Y-%m-%d")?;
+
+        let logs = task
+            .logs
+            .iter()
+            .map(LogEntry::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            id: task.id,
+            action: task.action.clone(),
+            count_per_day: task.count_per_day,
+            unit: task.unit.clone(),
+            logs,
+            start,
+            end: NaiveDate::parse_from_str(&task.end, "%Y-%m-%d")?,
+            effective_dow: task
+                .effective_dow
+                .iter()
+                .map(|s| {
+                    s.parse::<Weekday>()
+                        .map_err(|_| TaskError::InvalidWeekday(s.clone()))
+                })
+                .collect::<Result<_, _>>()?,
+            daily_tasks: task.daily_tasks.clone(),
+            name: task.name.clone(),
+            archive: task.archive,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_task_without_a_name_is_labelled_by_what_it_asks_for() {
+        let mut task = MyTask {
+            id: 1,
+            action: "Read".to_string(),
+            count_per_day: 20.0,
+            unit: "pages".to_string(),
+            logs: vec![],
+            start: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            end: NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(),
+            effective_dow: vec![Weekday::Mon],
+            daily_tasks: None,
+            name: None,
+            archive: false,
+        };
+        assert_eq!(task.label(), "Read 20 pages");
+
+        task.name = Some("Novel club".to_string());
+        assert_eq!(task.label(), "Novel club");
+    }
+}
@@ -0,0 +1,155 @@
+use stay_ahead_core::migration::{MigrationError, SCHEMA_VERSION};
+use stay_ahead_core::serializable_log_entry::SerializableLogEntry;
+use stay_ahead_core::{MyTask, SerializableState};
+
+/*
+ * Every format the app has ever saved or pushed, read by the current version
+ */
+
+const V0_BASELINE: &str = include_str!("fixtures/v0_baseline.json");
+const V0_WITH_LOGS: &str = include_str!("fixtures/v0_with_logs.json");
+const V0_GIST_SECTION: &str = include_str!("fixtures/v0_gist_section.json");
+const V1_CURRENT: &str = include_str!("fixtures/v1_current.json");
+const FUTURE_SCHEMA: &str = include_str!("fixtures/future_schema.json");
+
+const LEGACY_PAT: &str = "ghp_legacyToken123";
+const GIST_ID: &str = "aa5a315d61ae9438b18d";
+const GIST_FILE_NAME: &str = "stay_ahead.json";
+const ALL_DAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+fn assert_legacy_gist(state: &SerializableState) {
+    assert_eq!(state.gist.gist_id.as_deref(), Some(GIST_ID));
+    assert_eq!(state.gist.gist_file_name.as_deref(), Some(GIST_FILE_NAME));
+
+    // XOR-encoded PATs are read with any device key, and encrypted on the next save
+    let mut state = state.clone();
+    state.decrypt_secrets(&[7u8; 32]);
+    assert_eq!(state.gist.github_pat.as_deref(), Some(LEGACY_PAT));
+}
+
+fn assert_two_logged_entries(state: &SerializableState) {
+    let task = &state.tasks.as_ref().unwrap()[0];
+
+    assert_eq!(
+        task.logs,
+        vec![
+            SerializableLogEntry {
+                id: 1717210000000,
+                amount: 10.0,
+                timestamp: "2024-06-01 08:00:00".to_string(),
+                note: None,
+            },
+            SerializableLogEntry {
+                id: 1717220000000,
+                amount: 2.5,
+                timestamp: "2024-06-02 21:30:00".to_string(),
+                note: Some("On the train".to_string()),
+            },
+        ]
+    );
+    assert_eq!(MyTask::try_from(task).unwrap().count_accum(), 12.5);
+}
+
+#[test]
+fn v0_baseline_keeps_its_total_and_moves_gist_settings() {
+    let state = SerializableState::from_json(V0_BASELINE).unwrap();
+
+    assert_eq!(state.schema_version, SCHEMA_VERSION);
+    assert!(state.quarantine.is_empty());
+    assert_legacy_gist(&state);
+
+    let tasks = state.tasks.as_ref().unwrap();
+    assert_eq!(tasks.len(), 2);
+
+    // The total becomes a single entry on the start date
+    let habit = &tasks[0];
+    assert_eq!(habit.effective_dow, ALL_DAYS);
+    assert!(!habit.archive);
+    assert_eq!(
+        habit.logs,
+        vec![SerializableLogEntry {
+            id: 1717200000000,
+            amount: 12.5,
+            timestamp: "2024-06-01 00:00:00".to_string(),
+            note: Some("Progress logged before history was kept".to_string()),
+        }]
+    );
+    assert_eq!(MyTask::try_from(habit).unwrap().count_accum(), 12.5);
+
+    // Fields that were already there are kept, and nothing logged stays nothing logged
+    let goal = &tasks[1];
+    assert_eq!(goal.effective_dow, ["Monday", "Wednesday"]);
+    assert!(goal.archive);
+    assert_eq!(goal.name.as_deref(), Some("Couch to 5k"));
+    assert_eq!(
+        goal.daily_tasks,
+        Some(vec!["Warm up".to_string(), "Run 5k".to_string()])
+    );
+    assert!(goal.logs.is_empty());
+}
+
+#[test]
+fn v0_with_logs_keeps_its_entries() {
+    let state = SerializableState::from_json(V0_WITH_LOGS).unwrap();
+
+    assert_eq!(state.schema_version, SCHEMA_VERSION);
+    assert_legacy_gist(&state);
+    assert_two_logged_entries(&state);
+}
+
+#[test]
+fn v0_with_a_gist_section_is_read_as_it_is() {
+    let state = SerializableState::from_json(V0_GIST_SECTION).unwrap();
+
+    assert_eq!(state.schema_version, SCHEMA_VERSION);
+    assert_legacy_gist(&state);
+    assert_two_logged_entries(&state);
+}
+
+#[test]
+fn current_schema_round_trips_unchanged() {
+    let state = SerializableState::from_json(V1_CURRENT).unwrap();
+
+    assert_eq!(state.schema_version, SCHEMA_VERSION);
+    assert_eq!(state.backup_count, Some(5));
+    assert_eq!(state.pace_warning_multiple, Some(1.5));
+    assert_two_logged_entries(&state);
+
+    let expected: serde_json::Value = serde_json::from_str(V1_CURRENT).unwrap();
+    assert_eq!(serde_json::to_value(&state).unwrap(), expected);
+}
+
+#[test]
+fn migrating_twice_changes_nothing() {
+    let once = SerializableState::from_json(V0_BASELINE).unwrap();
+    let twice = SerializableState::from_json(&serde_json::to_string(&once).unwrap()).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&twice).unwrap(),
+        serde_json::to_value(&once).unwrap()
+    );
+}
+
+#[test]
+fn newer_schema_is_rejected() {
+    assert!(matches!(
+        SerializableState::from_json(FUTURE_SCHEMA),
+        Err(MigrationError::NewerSchema(99))
+    ));
+}
+
+#[test]
+fn state_that_is_not_an_object_is_rejected() {
+    assert!(matches!(
+        SerializableState::from_json("[]"),
+        Err(MigrationError::Failed(0, _))
+    ));
+}
@@ -0,0 +1,164 @@
+use proptest::collection::{hash_map, vec};
+use proptest::prelude::*;
+use serde_json::{json, Value};
+use stay_ahead_core::serializable_log_entry::SerializableLogEntry;
+use stay_ahead_core::serializable_task::SerializableTask;
+use stay_ahead_core::{MyTask, SerializableState};
+
+/*
+ * Whatever is found in a file, a synced state or an import, reading it never
+ * panics, and a task that cannot be read is set aside instead of dropped
+ */
+
+fn any_json() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        any::<f64>().prop_map(Value::from),
+        ".{0,12}".prop_map(Value::from),
+    ];
+
+    leaf.prop_recursive(4, 64, 8, |inner| {
+        prop_oneof![
+            vec(inner.clone(), 0..8).prop_map(Value::from),
+            hash_map("[a-z_]{1,14}", inner, 0..8)
+                .prop_map(|map| Value::Object(map.into_iter().collect())),
+        ]
+    })
+}
+
+// Mostly well-formed, so that both readable and unreadable values come up
+fn date_like() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "20[0-9]{2}-[01][0-9]-[0-3][0-9]",
+        any::<String>(),
+    ]
+}
+
+fn weekday_like() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just("Monday".to_string()),
+        Just("Sunday".to_string()),
+        "[A-Za-z]{0,9}",
+    ]
+}
+
+fn any_task() -> impl Strategy<Value = SerializableTask> {
+    (
+        any::<i64>(),
+        date_like(),
+        date_like(),
+        vec(weekday_like(), 0..8),
+        vec((any::<i64>(), any::<f32>(), date_like()), 0..4),
+    )
+        .prop_map(|(id, start, end, effective_dow, logs)| SerializableTask {
+            id,
+            action: "Read".to_string(),
+            count_per_day: 1.0,
+            unit: "pages".to_string(),
+            count_accum: 0.0,
+            logs: logs
+                .into_iter()
+                .map(|(id, amount, date)| SerializableLogEntry {
+                    id,
+                    amount,
+                    timestamp: format!("{} 08:00:00", date),
+                    note: None,
+                })
+                .collect(),
+            start,
+            end,
+            effective_dow,
+            daily_tasks: None,
+            name: None,
+            archive: false,
+        })
+}
+
+fn readable_task(id: i64) -> Value {
+    json!({
+        "id": id,
+        "action": "Read",
+        "count_per_day": 10.0,
+        "unit": "pages",
+        "count_accum": 0.0,
+        "start": "2026-01-01",
+        "end": "2026-01-31",
+    })
+}
+
+proptest! {
+    #[test]
+    fn reading_any_json_never_panics(value in any_json()) {
+        let _ = SerializableState::from_value(value);
+    }
+
+    #[test]
+    fn converting_any_task_never_panics(task in any_task()) {
+        let is_date = |s: &str| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok();
+        let readable = is_date(&task.start)
+            && is_date(&task.end)
+            && task.effective_dow.iter().all(|d| d.parse::<chrono::Weekday>().is_ok())
+            && task.logs.iter().all(|entry| {
+                chrono::NaiveDateTime::parse_from_str(&entry.timestamp, "%Y-%m-%d %H:%M:%S").is_ok()
+            });
+
+        prop_assert_eq!(MyTask::try_from(&task).is_ok(), readable);
+    }
+
+    #[test]
+    fn unparsable_tasks_are_quarantined(
+        junk in vec(any_json(), 0..6),
+        n_readable in 0..4i64,
+        schema_version in prop_oneof![Just(None), Just(Some(1))],
+    ) {
+        let mut tasks: Vec<Value> = (0..n_readable).map(readable_task).collect();
+        tasks.extend(junk.iter().cloned());
+
+        let mut state = json!({ "tasks": tasks });
+        if let Some(version) = schema_version {
+            state["schema_version"] = json!(version);
+        }
+
+        let state = SerializableState::from_value(state).unwrap();
+        let read = state.tasks.as_ref().unwrap();
+
+        prop_assert_eq!(read.len() + state.quarantine.len(), n_readable as usize + junk.len());
+        for id in 0..n_readable {
+            prop_assert!(read.iter().any(|task| task.id == id));
+        }
+    }
+
+    #[test]
+    fn tasks_that_fail_to_convert_are_quarantined(tasks in vec(any_task(), 0..6)) {
+        let n_convertible = tasks.iter().filter(|t| MyTask::try_from(*t).is_ok()).count();
+
+        let mut state = SerializableState {
+            tasks: Some(tasks.clone()),
+            ..Default::default()
+        };
+        let read = state.read_tasks().unwrap();
+
+        // Tasks with the same ID replace each other, the quarantine keeps every one
+        prop_assert!(read.len() <= n_convertible);
+        prop_assert_eq!(state.quarantine.len(), tasks.len() - n_convertible);
+    }
+}
+
+#[test]
+fn a_task_with_a_bad_date_keeps_its_raw_json_in_quarantine() {
+    let mut task = readable_task(1);
+    task["start"] = json!("the first of June");
+
+    let mut state = SerializableState::from_value(json!({
+        "schema_version": 1,
+        "tasks": [readable_task(2), task],
+    }))
+    .unwrap();
+    let read = state.read_tasks().unwrap();
+
+    assert_eq!(read.keys().copied().collect::<Vec<_>>(), vec![2]);
+    assert_eq!(state.quarantine.len(), 1);
+    assert_eq!(state.quarantine[0].raw["start"], json!("the first of June"));
+}